@@ -4,6 +4,9 @@ use std::{env, path::PathBuf};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("cargo:rerun-if-changed=wrapper.hpp");
+    println!("cargo:rerun-if-changed=shim/libfive_rs.h");
+    println!("cargo:rerun-if-changed=shim/libfive_rs.cpp");
+    println!("cargo:rerun-if-env-changed=EIGEN3_INCLUDE_DIR");
 
     // Dependencies -----------------------------------------------------------
     // TODO: include & build deps.
@@ -46,6 +49,32 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let mut libfive_lib_path = libfive_path;
         libfive_lib_path.push("lib");
 
+        // Additions to the C API. They use libfive's C++ API, which needs
+        // Eigen. Its headers are searched for in `EIGEN3_INCLUDE_DIR` and
+        // the usual system locations. As this emits its own link line it
+        // must come before libfive's.
+        let mut shim_builder = cc::Build::new();
+        shim_builder
+            .cpp(true)
+            .flag_if_supported("-std=c++17")
+            .flag_if_supported("/std:c++17")
+            .file("shim/libfive_rs.cpp")
+            .include(&libfive_include_path)
+            .include(libfive_base_path.join("include"));
+        if let Ok(eigen_include_path) = env::var("EIGEN3_INCLUDE_DIR") {
+            shim_builder.include(eigen_include_path);
+        }
+        for eigen_include_path in [
+            "/usr/include/eigen3",
+            "/usr/local/include/eigen3",
+            "/opt/homebrew/include/eigen3",
+        ] {
+            if PathBuf::from(eigen_include_path).exists() {
+                shim_builder.include(eigen_include_path);
+            }
+        }
+        shim_builder.compile("libfive_rs");
+
         // Emit linker searchpath
         println!("cargo:rustc-link-search={}", libfive_lib_path.display());
         // Link to libfive
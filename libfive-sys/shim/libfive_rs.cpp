@@ -0,0 +1,199 @@
+/*
+ *  Additions to the libfive C API, see libfive_rs.h.
+ */
+#include "libfive_rs.h"
+
+#include <algorithm>
+#include <atomic>
+#include <map>
+#include <memory>
+
+#include "libfive/eval/deck.hpp"
+#include "libfive/eval/eval_array.hpp"
+#include "libfive/eval/eval_interval.hpp"
+#include "libfive/render/brep/mesh.hpp"
+#include "libfive/render/brep/progress.hpp"
+#include "libfive/render/brep/region.hpp"
+#include "libfive/render/brep/settings.hpp"
+#include "libfive/tree/opcode.hpp"
+#include "libfive/tree/tree.hpp"
+
+using namespace libfive;
+
+namespace {
+
+std::map<Tree::Id, float> var_values(libfive_vars vars)
+{
+    std::map<Tree::Id, float> values;
+    for (uint32_t i = 0; i < vars.size; ++i)
+    {
+        values[static_cast<Tree::Id>(vars.vars[i])] = vars.values[i];
+    }
+    return values;
+}
+
+/*
+ *  Forwards libfive's meshing progress to a libfive_rs_progress_callback
+ *  and cancels meshing once it returns false.
+ */
+class CallbackProgressHandler : public ProgressHandler
+{
+public:
+    CallbackProgressHandler(libfive_rs_progress_callback callback,
+                            void* data, std::atomic_bool& cancel)
+        : callback(callback), data(data), cancel(cancel)
+    {
+        // Nothing to do here
+    }
+
+    void progress(double value) override
+    {
+        if (callback && !callback(data, static_cast<float>(value)))
+        {
+            cancel.store(true);
+        }
+    }
+
+protected:
+    libfive_rs_progress_callback callback;
+    void* data;
+    std::atomic_bool& cancel;
+};
+
+}   // anonymous namespace
+
+struct libfive_rs_evaluator_
+{
+    libfive_rs_evaluator_(const Tree& tree,
+                          const std::map<Tree::Id, float>& vars)
+        : deck(std::make_shared<Deck>(tree)), array(deck, vars),
+          interval(deck, vars)
+    {
+        // Nothing to do here
+    }
+
+    std::shared_ptr<Deck> deck;
+    ArrayEvaluator array;
+    IntervalEvaluator interval;
+};
+
+libfive_rs_evaluator libfive_rs_evaluator_new(libfive_tree tree,
+                                              libfive_vars vars)
+{
+    return new libfive_rs_evaluator_(*tree, var_values(vars));
+}
+
+void libfive_rs_evaluator_delete(libfive_rs_evaluator evaluator)
+{
+    delete evaluator;
+}
+
+bool libfive_rs_evaluator_update_vars(libfive_rs_evaluator evaluator,
+                                      libfive_vars vars)
+{
+    bool changed = false;
+    for (const auto& v : var_values(vars))
+    {
+        changed |= evaluator->array.setVar(v.first, v.second);
+        evaluator->interval.setVar(v.first, v.second);
+    }
+    return changed;
+}
+
+void libfive_rs_evaluator_eval(libfive_rs_evaluator evaluator,
+                               const libfive_vec3* points, uint32_t count,
+                               float* values)
+{
+    for (uint32_t start = 0; start < count; start += ArrayEvaluator::N)
+    {
+        const uint32_t n = std::min<uint32_t>(ArrayEvaluator::N,
+                                              count - start);
+        for (uint32_t i = 0; i < n; ++i)
+        {
+            const auto& p = points[start + i];
+            evaluator->array.set({p.x, p.y, p.z}, i);
+        }
+
+        const auto out = evaluator->array.values(n);
+        for (uint32_t i = 0; i < n; ++i)
+        {
+            values[start + i] = out(i);
+        }
+    }
+}
+
+libfive_interval libfive_rs_evaluator_eval_interval(
+        libfive_rs_evaluator evaluator, libfive_region3 region)
+{
+    const auto result = evaluator->interval.eval(
+            {region.X.lower, region.Y.lower, region.Z.lower},
+            {region.X.upper, region.Y.upper, region.Z.upper});
+    return {static_cast<float>(result.lower()),
+            static_cast<float>(result.upper())};
+}
+
+libfive_tree libfive_rs_tree_flatten(libfive_tree tree)
+{
+    return new Tree(tree->flatten());
+}
+
+int libfive_rs_tree_op(libfive_tree tree)
+{
+    return tree->op();
+}
+
+libfive_tree libfive_rs_tree_lhs(libfive_tree tree)
+{
+    return Opcode::args(tree->op()) >= 1 ? new Tree(tree->lhs()) : nullptr;
+}
+
+libfive_tree libfive_rs_tree_rhs(libfive_tree tree)
+{
+    return Opcode::args(tree->op()) >= 2 ? new Tree(tree->rhs()) : nullptr;
+}
+
+libfive_mesh* libfive_rs_tree_render_mesh(libfive_tree tree,
+                                          libfive_region3 region,
+                                          float resolution,
+                                          libfive_rs_progress_callback callback,
+                                          void* data)
+{
+    BRepSettings settings;
+    settings.min_feature = 1 / resolution;
+
+    CallbackProgressHandler handler(callback, data, settings.cancel);
+    handler.progress(0);
+    if (settings.cancel.load())
+    {
+        return nullptr;
+    }
+    settings.progress_handler = &handler;
+
+    const Region<3> bounds({region.X.lower, region.Y.lower, region.Z.lower},
+                           {region.X.upper, region.Y.upper, region.Z.upper});
+    const auto mesh = Mesh::render(*tree, bounds, settings);
+    if (!mesh || settings.cancel.load())
+    {
+        return nullptr;
+    }
+
+    // Laid out like the meshes libfive_tree_render_mesh returns.
+    auto out = new libfive_mesh;
+    out->verts = new libfive_vec3[mesh->verts.size()];
+    for (unsigned i = 0; i < mesh->verts.size(); ++i)
+    {
+        out->verts[i] = {mesh->verts[i].x(), mesh->verts[i].y(),
+                         mesh->verts[i].z()};
+    }
+    out->vert_count = mesh->verts.size();
+
+    out->tris = new libfive_tri[mesh->branes.size()];
+    for (unsigned i = 0; i < mesh->branes.size(); ++i)
+    {
+        out->tris[i] = {mesh->branes[i][0], mesh->branes[i][1],
+                        mesh->branes[i][2]};
+    }
+    out->tri_count = mesh->branes.size();
+
+    return out;
+}
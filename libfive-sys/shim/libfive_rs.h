@@ -0,0 +1,93 @@
+/*
+ *  Additions to the libfive C API, for what it offers no call for.
+ */
+#pragma once
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#include <libfive.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ *  An evaluator that is set up once for a tree and the values of its
+ *  variables. It then evaluates the tree at many points or over many
+ *  regions without rebuilding its tape.
+ *
+ *  An evaluator must not be used from several threads at once.
+ */
+typedef struct libfive_rs_evaluator_* libfive_rs_evaluator;
+
+/*
+ *  Creates an evaluator for the tree with the given variable values.
+ *  Delete it with libfive_rs_evaluator_delete.
+ */
+libfive_rs_evaluator libfive_rs_evaluator_new(libfive_tree tree,
+                                              libfive_vars vars);
+
+void libfive_rs_evaluator_delete(libfive_rs_evaluator evaluator);
+
+/*
+ *  Sets the values of the evaluator's variables. Variables the evaluator
+ *  was not created with are ignored. Returns true if any value changed.
+ */
+bool libfive_rs_evaluator_update_vars(libfive_rs_evaluator evaluator,
+                                      libfive_vars vars);
+
+/*
+ *  Evaluates the tree at count points and writes the results to values,
+ *  which must hold count floats. Points are evaluated in batches.
+ */
+void libfive_rs_evaluator_eval(libfive_rs_evaluator evaluator,
+                               const libfive_vec3* points, uint32_t count,
+                               float* values);
+
+/*
+ *  Evaluates the tree over the region using interval arithmetic.
+ */
+libfive_interval libfive_rs_evaluator_eval_interval(
+        libfive_rs_evaluator evaluator, libfive_region3 region);
+
+/*
+ *  Returns the tree with all remapped subtrees applied, such that the
+ *  operands of each node are its actual children.
+ *  Delete it with libfive_tree_delete.
+ */
+libfive_tree libfive_rs_tree_flatten(libfive_tree tree);
+
+/*
+ *  Returns the opcode of the tree's root node, see libfive_opcode_enum.
+ */
+int libfive_rs_tree_op(libfive_tree tree);
+
+/*
+ *  Return the first resp. second operand of the tree's root node or NULL
+ *  if it has none. Delete them with libfive_tree_delete.
+ */
+libfive_tree libfive_rs_tree_lhs(libfive_tree tree);
+libfive_tree libfive_rs_tree_rhs(libfive_tree tree);
+
+/*
+ *  Called with the meshing progress, from 0 to 1, possibly from one of
+ *  libfive's threads. Meshing is cancelled once it returns false.
+ */
+typedef bool (*libfive_rs_progress_callback)(void* data, float progress);
+
+/*
+ *  Like libfive_tree_render_mesh but reports progress to callback, if
+ *  not NULL, and stops once it returns false. The callback is called with
+ *  0 before meshing starts. Returns NULL if meshing was cancelled.
+ *  Delete the mesh with libfive_mesh_delete.
+ */
+libfive_mesh* libfive_rs_tree_render_mesh(libfive_tree tree,
+                                          libfive_region3 region,
+                                          float resolution,
+                                          libfive_rs_progress_callback callback,
+                                          void* data);
+
+#ifdef __cplusplus
+}
+#endif
@@ -1,2 +1,3 @@
 #include <libfive.h>
 #include <libfive_stdlib.h>
+#include "shim/libfive_rs.h"
@@ -1,4 +1,5 @@
 import os
+import subprocess
 from datetime import datetime
 from itertools import repeat
 
@@ -12,7 +13,7 @@ def arg_in(a, use_self):
         else:
             return '{}: Tree'.format(a.name)
     elif a.type == 'tfloat':
-        return '{}: TreeF32'.format(a.name)
+        return '{}: impl Into<TreeFloat>'.format(a.name)
     elif a.type == 'tvec2':
         return '{}: TreeVec2'.format(a.name)
     elif a.type == 'tvec3':
@@ -31,7 +32,7 @@ def arg_out(a, use_self):
         else:
             return '{}.0'.format(a.name)
     elif a.type == 'tfloat':
-        return '{}.0'.format(a.name)
+        return '{}.into().0'.format(a.name)
     elif a.type == 'tvec2':
         return 'sys::tvec2 {{ x: {0}.x.0, y: {0}.y.0 }}'.format(a.name)
     elif a.type == 'tvec3':
@@ -131,15 +132,35 @@ for m in ['shapes', 'generators']:
         write_header(f, m)
         f.write(format_module_generator(stdlib, m))
 
-f = open('../bind/rust/libfive/stdlib/text.rs', 'w')
-f.write(
+with open('../bind/rust/libfive/stdlib/text.rs', 'w') as f:
+    f.write(
 '''
 /// # Text <a name="text"></a>
 impl Tree {
-    pub fn text(txt: impl Into<Vec<u8>>, pos: TreeVec2) -> Self {
-        let txt = std::ffi::CString::new(txt).unwrap();
-        Self(unsafe { sys::text(txt.as_ptr(), sys::tvec2 { x: pos.x.0, y: pos.y.0 }) })
+    /// Renders `txt` with its baseline starting at `pos`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidText`] if `txt` contains a NUL byte.
+    pub fn text(txt: impl Into<Vec<u8>>, pos: TreeVec2) -> Result<Self> {
+        let txt =
+            std::ffi::CString::new(txt).map_err(|_| Error::InvalidText)?;
+        Ok(Self(unsafe {
+            sys::text(
+                txt.as_ptr(),
+                sys::tvec2 {
+                    x: pos.x.0,
+                    y: pos.y.0,
+                },
+            )
+        }))
     }
 }
 '''
 )
+
+# Wrap long signatures & calls to the `max_width` of the `rustfmt.toml`.
+subprocess.run(['rustfmt', '--edition', '2021'] + [
+    '../bind/rust/libfive/stdlib/%s.rs' % m
+    for m in ['csg', 'transforms', 'shapes', 'generators', 'text']
+], check=True)
@@ -0,0 +1,83 @@
+use crate::*;
+
+/// Evaluator of a [`Tree`] that is set up once and then evaluates it at
+/// many points, in batches.
+///
+/// `libfive`'s C API only evaluates single points and builds a new
+/// evaluator for each. This uses the `libfive_rs_evaluator` that
+/// `libfive-sys` adds.
+pub(crate) struct BatchEvaluator(sys::libfive_rs_evaluator);
+
+impl BatchEvaluator {
+    /// Creates an evaluator for `tree` with the current values of
+    /// `variables`.
+    pub(crate) fn new(tree: &Tree, variables: &Variables) -> Self {
+        Self(unsafe {
+            sys::libfive_rs_evaluator_new(tree.0, variables.sys_variables())
+        })
+    }
+
+    /// Sets the values of the evaluator's variables to those in
+    /// `variables` and returns whether any value changed.
+    pub(crate) fn update(&mut self, variables: &Variables) -> bool {
+        unsafe {
+            sys::libfive_rs_evaluator_update_vars(
+                self.0,
+                variables.sys_variables(),
+            )
+        }
+    }
+
+    /// Evaluates the tree at each of `points`.
+    pub(crate) fn eval(&mut self, points: &[[f32; 3]]) -> Vec<f32> {
+        let points = points
+            .iter()
+            .map(|point| sys::libfive_vec3 {
+                x: point[0],
+                y: point[1],
+                z: point[2],
+            })
+            .collect::<Vec<_>>();
+        let mut values = vec![0.0; points.len()];
+
+        // Keep each call's count within `u32`.
+        for (points, values) in points
+            .chunks(u32::MAX as usize)
+            .zip(values.chunks_mut(u32::MAX as usize))
+        {
+            unsafe {
+                sys::libfive_rs_evaluator_eval(
+                    self.0,
+                    points.as_ptr(),
+                    points.len() as u32,
+                    values.as_mut_ptr(),
+                )
+            };
+        }
+
+        values
+    }
+
+    /// Evaluates the tree over `region` using interval arithmetic.
+    pub(crate) fn eval_interval(&mut self, region: &Region3) -> Interval {
+        let interval = unsafe {
+            sys::libfive_rs_evaluator_eval_interval(self.0, region.0)
+        };
+
+        Interval {
+            lower: interval.lower,
+            upper: interval.upper,
+        }
+    }
+
+    /// See [`Tree::region_state()`].
+    pub(crate) fn region_state(&mut self, region: &Region3) -> RegionState {
+        RegionState::from(self.eval_interval(region))
+    }
+}
+
+impl Drop for BatchEvaluator {
+    fn drop(&mut self) {
+        unsafe { sys::libfive_rs_evaluator_delete(self.0) };
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+use std::rc::Rc;
+
+/// Cache of meshes, keyed by [tree](Tree::id), region and resolution.
+///
+/// Useful when the same shapes are meshed over and over, e.g. in generative
+/// applications.
+///
+/// The cache holds on to each tree it meshed. This keeps the tree's id from
+/// being reused by another tree, which would otherwise return a wrong mesh.
+///
+/// # Invalidation
+///
+/// A tree's id only identifies its structure. A tree containing
+/// [variables](Variables) is meshed with the values they have at the time.
+/// Changing them later, e.g. through an [`Evaluator`], does not change the
+/// key. Call [`clear()`](MeshCache::clear) to drop such stale meshes.
+#[derive(Default)]
+pub struct MeshCache {
+    meshes: HashMap<(Tree, [u32; 7]), Rc<FlatTriangleMesh>>,
+    misses: usize,
+}
+
+impl MeshCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the mesh of `tree` in `region` at `resolution`, meshing it
+    /// with [`Tree::mesh_into()`] unless it is cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero and [`Error::EmptyResult`] if `libfive`
+    /// produced no mesh. Failures are not cached.
+    pub fn get_or_mesh(
+        &mut self,
+        tree: &Tree,
+        region: &Region3,
+        resolution: f32,
+    ) -> Result<Rc<FlatTriangleMesh>> {
+        let (min, max) = (region.min(), region.max());
+        let key = (
+            tree.clone(),
+            [min[0], min[1], min[2], max[0], max[1], max[2], resolution]
+                .map(f32::to_bits),
+        );
+
+        if let Some(mesh) = self.meshes.get(&key) {
+            return Ok(mesh.clone());
+        }
+
+        self.misses += 1;
+        let mut mesh = FlatTriangleMesh {
+            positions: Vec::new(),
+            triangles: Vec::new(),
+        };
+        tree.mesh_into(
+            region,
+            resolution,
+            &mut mesh.positions,
+            &mut mesh.triangles,
+        )?;
+
+        let mesh = Rc::new(mesh);
+        self.meshes.insert(key, mesh.clone());
+
+        Ok(mesh)
+    }
+
+    /// Returns the number of meshes in the cache.
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    /// Returns `true` if the cache holds no meshes.
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    /// Returns how often [`get_or_mesh()`](MeshCache::get_or_mesh) had to
+    /// mesh a tree because it was not cached.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Removes all meshes and the trees they were made from.
+    pub fn clear(&mut self) {
+        self.meshes.clear();
+    }
+}
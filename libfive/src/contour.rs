@@ -0,0 +1,136 @@
+use crate::*;
+
+/// Returns the length of the `contour`.
+///
+/// The contour is treated as closed. If its last point differs from its
+/// first one the closing segment is included.
+pub fn contour_length<T: Point2>(contour: &Contour<T>) -> f32 {
+    closing_segments(contour)
+        .map(|(a, b)| (b.x() - a.x()).hypot(b.y() - a.y()))
+        .sum()
+}
+
+/// Returns the signed area enclosed by the `contour`.
+///
+/// The area is positive if the contour winds counter-clockwise and negative
+/// if it winds clockwise. For the contours of a slice, which never cross,
+/// holes are wound opposite to the outlines enclosing them.
+///
+/// The contour is treated as closed, like with [`contour_length()`].
+pub fn contour_signed_area<T: Point2>(contour: &Contour<T>) -> f32 {
+    // Shoelace formula.
+    0.5 * closing_segments(contour)
+        .map(|(a, b)| a.x() * b.y() - b.x() * a.y())
+        .sum::<f32>()
+}
+
+/// Returns the points of `contour` and whether it is closed.
+///
+/// A contour is closed if its last point equals its first one. The repeated
+/// point is then left out.
+pub(crate) fn split_closed<T: Point2>(contour: &Contour<T>) -> (&[T], bool) {
+    match contour.as_slice() {
+        [first, .., last] if first.x() == last.x() && first.y() == last.y() => {
+            (&contour[..contour.len() - 1], true)
+        }
+        points => (points, false),
+    }
+}
+
+/// Returns the segments of `contour`, including the one from its last back
+/// to its first point.
+fn closing_segments<T: Point2>(
+    contour: &Contour<T>,
+) -> impl Iterator<Item = (&T, &T)> {
+    contour.iter().zip(contour.iter().cycle().skip(1))
+}
+
+/// Position of a contour in the containment hierarchy of a set of contours.
+///
+/// See [`classify_contours()`].
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ContourNode {
+    /// Index of the innermost contour enclosing this one, if any.
+    pub parent: Option<usize>,
+    /// Indices of the contours directly enclosed by this one.
+    pub children: Vec<usize>,
+    /// Number of contours enclosing this one.
+    pub depth: usize,
+}
+
+impl ContourNode {
+    /// Returns `true` if the contour is a hole, i.e. if it is enclosed by an
+    /// odd number of contours.
+    ///
+    /// This is the even-odd fill rule.
+    pub fn is_hole(&self) -> bool {
+        self.depth % 2 == 1
+    }
+}
+
+/// Builds the containment hierarchy of `contours`.
+///
+/// The result holds one [`ContourNode`] per contour, in the same order.
+/// Contours without a parent are outlines. Their children are holes, whose
+/// children are islands inside these holes, and so on.
+///
+/// The contours must not cross each other, which is the case for the
+/// contours of a slice. Containment is then decided by testing a single
+/// point of each contour against the others. Candidate parents are ranked
+/// by the magnitude of their [signed area](contour_signed_area).
+///
+/// This takes time quadratic in the number of contours.
+pub fn classify_contours<T: Point2>(
+    contours: &[Contour<T>],
+) -> Vec<ContourNode> {
+    let areas = contours
+        .iter()
+        .map(|contour| contour_signed_area(contour).abs())
+        .collect::<Vec<_>>();
+
+    let mut nodes = vec![ContourNode::default(); contours.len()];
+
+    for (index, contour) in contours.iter().enumerate() {
+        let Some(point) = contour.first() else {
+            continue;
+        };
+
+        let containers = (0..contours.len())
+            .filter(|&other| {
+                other != index
+                    && areas[other] > areas[index]
+                    && contains(&contours[other], point)
+            })
+            .collect::<Vec<_>>();
+
+        nodes[index].depth = containers.len();
+        nodes[index].parent = containers
+            .into_iter()
+            .min_by(|&a, &b| areas[a].total_cmp(&areas[b]));
+    }
+
+    let parents = nodes.iter().map(|node| node.parent).collect::<Vec<_>>();
+    for (index, parent) in parents.into_iter().enumerate() {
+        if let Some(parent) = parent {
+            nodes[parent].children.push(index);
+        }
+    }
+
+    nodes
+}
+
+/// Returns `true` if `point` lies inside the closed `contour`, using the
+/// even-odd rule.
+fn contains<T: Point2>(contour: &Contour<T>, point: &T) -> bool {
+    closing_segments(contour)
+        .filter(|(a, b)| {
+            (a.y() > point.y()) != (b.y() > point.y())
+                && point.x()
+                    < a.x()
+                        + (point.y() - a.y()) * (b.x() - a.x())
+                            / (b.y() - a.y())
+        })
+        .count()
+        % 2
+        == 1
+}
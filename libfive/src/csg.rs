@@ -25,40 +25,52 @@ impl Tree {
         Self(unsafe { sys::difference(self.0, b.0) })
     }
 
-    pub fn offset(self, o: TreeFloat) -> Self {
-        Self(unsafe { sys::offset(self.0, o.0) })
+    pub fn offset(self, o: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::offset(self.0, o.into().0) })
     }
 
-    pub fn clearance(self, b: Tree, offset: TreeFloat) -> Self {
-        Self(unsafe { sys::clearance(self.0, b.0, offset.0) })
+    pub fn clearance(self, b: Tree, offset: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::clearance(self.0, b.0, offset.into().0) })
     }
 
-    pub fn shell(self, offset: TreeFloat) -> Self {
-        Self(unsafe { sys::shell(self.0, offset.0) })
+    pub fn shell(self, offset: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::shell(self.0, offset.into().0) })
     }
 
-    pub fn blend_expt(self, b: Tree, m: TreeFloat) -> Self {
-        Self(unsafe { sys::blend_expt(self.0, b.0, m.0) })
+    pub fn blend_expt(self, b: Tree, m: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::blend_expt(self.0, b.0, m.into().0) })
     }
 
-    pub fn blend_expt_unit(self, b: Tree, m: TreeFloat) -> Self {
-        Self(unsafe { sys::blend_expt_unit(self.0, b.0, m.0) })
+    pub fn blend_expt_unit(self, b: Tree, m: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::blend_expt_unit(self.0, b.0, m.into().0) })
     }
 
-    pub fn blend_rough(self, b: Tree, m: TreeFloat) -> Self {
-        Self(unsafe { sys::blend_rough(self.0, b.0, m.0) })
+    pub fn blend_rough(self, b: Tree, m: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::blend_rough(self.0, b.0, m.into().0) })
     }
 
-    pub fn blend_difference(self, b: Tree, m: TreeFloat, o: TreeFloat) -> Self {
-        Self(unsafe { sys::blend_difference(self.0, b.0, m.0, o.0) })
+    pub fn blend_difference(
+        self,
+        b: Tree,
+        m: impl Into<TreeFloat>,
+        o: impl Into<TreeFloat>,
+    ) -> Self {
+        Self(unsafe {
+            sys::blend_difference(self.0, b.0, m.into().0, o.into().0)
+        })
     }
 
-    pub fn morph(self, b: Tree, m: TreeFloat) -> Self {
-        Self(unsafe { sys::morph(self.0, b.0, m.0) })
+    pub fn morph(self, b: Tree, m: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::morph(self.0, b.0, m.into().0) })
     }
 
-    pub fn loft(self, b: Tree, zmin: TreeFloat, zmax: TreeFloat) -> Self {
-        Self(unsafe { sys::loft(self.0, b.0, zmin.0, zmax.0) })
+    pub fn loft(
+        self,
+        b: Tree,
+        zmin: impl Into<TreeFloat>,
+        zmax: impl Into<TreeFloat>,
+    ) -> Self {
+        Self(unsafe { sys::loft(self.0, b.0, zmin.into().0, zmax.into().0) })
     }
 
     pub fn loft_between(
@@ -85,4 +97,3 @@ impl Tree {
         })
     }
 }
-
@@ -0,0 +1,48 @@
+use crate::*;
+use std::io::{self, Write};
+
+/// Writes `contours` to `writer` as an ASCII
+/// [`DXF`](https://en.wikipedia.org/wiki/AutoCAD_DXF) file.
+///
+/// The file is kept minimal: an `ENTITIES` section with one `POLYLINE` per
+/// contour on layer `0`, as understood by AutoCAD R12 and later. Closed
+/// contours, i.e. those whose last point equals their first one, are written
+/// as closed polylines without the repeated point.
+pub fn write_dxf<T: Point2>(
+    contours: &[Contour<T>],
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let mut group = |code: u32, value: &dyn std::fmt::Display| {
+        writeln!(writer, "{}\n{}", code, value)
+    };
+
+    group(0, &"SECTION")?;
+    group(2, &"ENTITIES")?;
+
+    for contour in contours {
+        let (points, closed) = crate::contour::split_closed(contour);
+
+        group(0, &"POLYLINE")?;
+        group(8, &0)?;
+        // Vertices follow.
+        group(66, &1)?;
+        group(70, &(closed as u32))?;
+        for code in [10, 20, 30] {
+            group(code, &0.0)?;
+        }
+
+        for point in points {
+            group(0, &"VERTEX")?;
+            group(8, &0)?;
+            group(10, &point.x())?;
+            group(20, &point.y())?;
+            group(30, &0.0)?;
+        }
+
+        group(0, &"SEQEND")?;
+        group(8, &0)?;
+    }
+
+    group(0, &"ENDSEC")?;
+    group(0, &"EOF")
+}
@@ -0,0 +1,353 @@
+use crate::*;
+use std::ffi::CStr;
+
+/// Returns the S-expression `libfive` prints for `tree`.
+pub(crate) fn sexpr(tree: &Tree) -> String {
+    unsafe {
+        let c_string = sys::libfive_tree_print(tree.0);
+        let string = CStr::from_ptr(c_string).to_string_lossy().into_owned();
+        sys::libfive_free_str(c_string);
+        string
+    }
+}
+
+/// Returns the expression of `tree` in the syntax of `libfive`'s Scheme
+/// bindings.
+///
+/// Operators `libfive` prints under another name than the bindings use are
+/// renamed and infinite and NaN constants are spelled the Scheme way.
+///
+/// Returns `None` if the tree contains nodes other than operations,
+/// constants and the coordinates, e.g. variables.
+pub(crate) fn scheme(tree: &Tree) -> Option<String> {
+    let text = sexpr(tree).replace('(', " ( ").replace(')', " ) ");
+    let mut scheme = String::with_capacity(text.len());
+    let mut operator = false;
+
+    for token in text.split_whitespace() {
+        let token = match token {
+            "atan2" if operator => "atan",
+            "nanfill" if operator => "nan-fill",
+            "neg" if operator => "-",
+            "recip" if operator => "/",
+            _ if operator => token,
+            "(" | ")" | "x" | "y" | "z" => token,
+            "inf" => "+inf.0",
+            "-inf" => "-inf.0",
+            _ => match token.parse::<f32>().ok()? {
+                value if value.is_nan() => "+nan.0",
+                _ => token,
+            },
+        };
+
+        if !(scheme.is_empty() || scheme.ends_with('(') || token == ")") {
+            scheme.push(' ');
+        }
+        scheme.push_str(token);
+        operator = token == "(";
+    }
+
+    Some(scheme)
+}
+
+/// A node of an [`Expr`].
+///
+/// Operands are indices of nodes that precede this one in the `Expr`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Node {
+    /// Bit pattern of an [`f32`] constant.
+    Constant(u32),
+    X,
+    Y,
+    Z,
+    /// A free variable. The tree it was built from is kept in the `Expr`.
+    Variable,
+    Unary(Op, usize),
+    Binary(Op, usize, usize),
+}
+
+/// The node graph of a [`Tree`].
+///
+/// Built by walking the tree's DAG. Each node is visited once, identified
+/// by its [id](Tree::id). Separately built nodes stay separate, even if
+/// they are structurally identical.
+pub(crate) struct Expr {
+    /// Nodes in topological order: operands precede their operations.
+    nodes: Vec<Node>,
+    /// The tree each node was built from.
+    trees: Vec<Tree>,
+    root: usize,
+}
+
+impl Expr {
+    /// Walks the DAG of `tree`.
+    ///
+    /// Returns `None` if the tree contains nodes that can not be rebuilt
+    /// through `libfive`'s C API, i.e. oracles.
+    pub(crate) fn from_tree(tree: &Tree) -> Option<Self> {
+        let mut expr = Self {
+            nodes: Vec::new(),
+            trees: Vec::new(),
+            root: 0,
+        };
+        let mut indices = HashMap::<usize, usize>::default();
+
+        // A tree and whether its operands were already pushed. Iterative
+        // rather than recursive so deep trees can't overflow the stack.
+        let mut stack = vec![(
+            Tree(unsafe { sys::libfive_rs_tree_flatten(tree.0) }),
+            false,
+        )];
+
+        while let Some((tree, expanded)) = stack.pop() {
+            if indices.contains_key(&tree.id()) {
+                continue;
+            }
+
+            let (lhs, rhs) = operands(&tree);
+
+            if !expanded {
+                stack.push((tree, true));
+                // The left operand is visited first.
+                stack.extend(
+                    rhs.into_iter().chain(lhs).map(|tree| (tree, false)),
+                );
+                continue;
+            }
+
+            let node = match (op(&tree)?, lhs, rhs) {
+                (Op::Constant, None, None) => {
+                    Node::Constant(tree.as_f32().ok()?.to_bits())
+                }
+                (Op::VarX, None, None) => Node::X,
+                (Op::VarY, None, None) => Node::Y,
+                (Op::VarZ, None, None) => Node::Z,
+                (Op::VarFree, None, None) => Node::Variable,
+                (Op::Oracle, ..) | (Op::Invalid, ..) => return None,
+                (op, Some(a), None) => Node::Unary(op, indices[&a.id()]),
+                (op, Some(a), Some(b)) => {
+                    Node::Binary(op, indices[&a.id()], indices[&b.id()])
+                }
+                _ => return None,
+            };
+
+            indices.insert(tree.id(), expr.nodes.len());
+            expr.nodes.push(node);
+            expr.trees.push(tree);
+        }
+
+        // The root is completed last.
+        expr.root = expr.nodes.len().checked_sub(1)?;
+
+        Some(expr)
+    }
+
+    /// Returns the number of unique nodes.
+    pub(crate) fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of nodes on the longest path from the root to a
+    /// leaf.
+    pub(crate) fn depth(&self) -> usize {
+        let mut depths = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            depths.push(match *node {
+                Node::Unary(_, a) => depths[a] + 1,
+                Node::Binary(_, a, b) => depths[a].max(depths[b]) + 1,
+                _ => 1,
+            });
+        }
+        depths[self.root]
+    }
+
+    /// Builds a [`Tree`] from the nodes, replacing operations whose operands
+    /// are all constant by their value.
+    ///
+    /// Variables whose [id](Tree::id) is in `variables` are replaced by
+    /// their value there first.
+    ///
+    /// Values are computed on [`f32`]s, the way `libfive` evaluates each
+    /// operation. Subtrees without anything to fold are reused as they are.
+    pub(crate) fn to_folded_tree(
+        &self,
+        variables: &HashMap<usize, f32>,
+    ) -> Tree {
+        // The value of each node that is constant.
+        let mut values = Vec::<Option<f32>>::with_capacity(self.nodes.len());
+        // The rebuilt tree of each node or `None` if it is unchanged.
+        let mut folded = Vec::<Option<Tree>>::with_capacity(self.nodes.len());
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            let value = match *node {
+                Node::Constant(bits) => Some(f32::from_bits(bits)),
+                Node::Variable => {
+                    variables.get(&self.trees[index].id()).copied()
+                }
+                Node::Unary(op, a) => values[a].and_then(|a| op.eval_unary(a)),
+                Node::Binary(op, a, b) => values[a]
+                    .zip(values[b])
+                    .and_then(|(a, b)| op.eval_binary(a, b)),
+                _ => None,
+            };
+
+            let tree = |index: usize| {
+                folded[index].as_ref().unwrap_or(&self.trees[index]).0
+            };
+            let tree = match (*node, value) {
+                (Node::Constant(_), _) => None,
+                (_, Some(value)) => Some(Tree::from(value)),
+                (Node::Unary(op, a), None) if folded[a].is_some() => {
+                    Some(Tree(unsafe {
+                        sys::libfive_tree_unary(op as _, tree(a))
+                    }))
+                }
+                (Node::Binary(op, a, b), None)
+                    if folded[a].is_some() || folded[b].is_some() =>
+                {
+                    Some(Tree(unsafe {
+                        sys::libfive_tree_binary(op as _, tree(a), tree(b))
+                    }))
+                }
+                _ => None,
+            };
+
+            values.push(value);
+            folded.push(tree);
+        }
+
+        folded
+            .swap_remove(self.root)
+            .unwrap_or_else(|| self.trees[self.root].clone())
+    }
+}
+
+/// Returns the opcode of the root node of `tree`.
+///
+/// Returns `None` for opcodes this crate does not know.
+pub(crate) fn op(tree: &Tree) -> Option<Op> {
+    Op::from_code(unsafe { sys::libfive_rs_tree_op(tree.0) })
+}
+
+/// Returns the operands of the root node of `tree`.
+fn operands(tree: &Tree) -> (Option<Tree>, Option<Tree>) {
+    let operand = |operand: sys::libfive_tree| {
+        (!operand.is_null()).then_some(Tree(operand))
+    };
+
+    unsafe {
+        (
+            operand(sys::libfive_rs_tree_lhs(tree.0)),
+            operand(sys::libfive_rs_tree_rhs(tree.0)),
+        )
+    }
+}
+
+impl Op {
+    /// Returns the operation with the opcode `code`.
+    fn from_code(code: i32) -> Option<Self> {
+        [
+            Op::Invalid,
+            Op::Constant,
+            Op::VarX,
+            Op::VarY,
+            Op::VarZ,
+            Op::VarFree,
+            Op::ConstVar,
+            Op::Square,
+            Op::Sqrt,
+            Op::Neg,
+            Op::Sin,
+            Op::Cos,
+            Op::Tan,
+            Op::Asin,
+            Op::Acos,
+            Op::Atan,
+            Op::Exp,
+            Op::Abs,
+            Op::Log,
+            Op::Recip,
+            Op::Add,
+            Op::Mul,
+            Op::Min,
+            Op::Max,
+            Op::Sub,
+            Op::Div,
+            Op::Atan2,
+            Op::Pow,
+            Op::NthRoot,
+            Op::Mod,
+            Op::NanFill,
+            Op::Compare,
+            Op::Oracle,
+        ]
+        .into_iter()
+        .find(|&op| op as i32 == code)
+    }
+
+    /// Applies the unary operation to `a` like `libfive` does.
+    ///
+    /// Returns `None` if this is not a unary operation.
+    fn eval_unary(self, a: f32) -> Option<f32> {
+        Some(match self {
+            Op::Square => a * a,
+            Op::Sqrt => a.sqrt(),
+            Op::Neg => -a,
+            Op::Sin => a.sin(),
+            Op::Cos => a.cos(),
+            Op::Tan => a.tan(),
+            Op::Asin => a.asin(),
+            Op::Acos => a.acos(),
+            Op::Atan => a.atan(),
+            Op::Exp => a.exp(),
+            Op::Abs => a.abs(),
+            Op::Log => a.ln(),
+            Op::Recip => 1.0 / a,
+            // Only freezes variables.
+            Op::ConstVar => a,
+            _ => return None,
+        })
+    }
+
+    /// Applies the binary operation to `a` and `b` like `libfive` does.
+    ///
+    /// Returns `None` if this is not a binary operation or if the result
+    /// is not defined.
+    fn eval_binary(self, a: f32, b: f32) -> Option<f32> {
+        Some(match self {
+            Op::Add => a + b,
+            Op::Mul => a * b,
+            Op::Min => a.min(b),
+            Op::Max => a.max(b),
+            Op::Sub => a - b,
+            Op::Div => a / b,
+            Op::Atan2 => a.atan2(b),
+            Op::Pow => a.powf(b),
+            Op::NthRoot => a.powf(1.0 / b),
+            // `libfive` adds `b` until the remainder is positive. This only
+            // terminates for a positive `b`.
+            Op::Mod => match a % b {
+                value if value < 0.0 && 0.0 < b => value + b,
+                value if value < 0.0 => return None,
+                value => value,
+            },
+            Op::NanFill => {
+                if a.is_nan() {
+                    b
+                } else {
+                    a
+                }
+            }
+            Op::Compare => {
+                if a < b {
+                    -1.0
+                } else if b < a {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => return None,
+        })
+    }
+}
@@ -1,7 +1,9 @@
 /// Generators
 impl Tree {
-    pub fn array_x(shape: Tree, nx: u32, dx: TreeFloat) -> Self {
-        Self(unsafe { sys::array_x(shape.0, nx.try_into().unwrap(), dx.0) })
+    pub fn array_x(shape: Tree, nx: u32, dx: impl Into<TreeFloat>) -> Self {
+        Self(unsafe {
+            sys::array_x(shape.0, nx.try_into().unwrap(), dx.into().0)
+        })
     }
 
     pub fn array_xy(shape: Tree, nx: u32, ny: u32, delta: TreeVec2) -> Self {
@@ -53,8 +55,11 @@ impl Tree {
         })
     }
 
-    pub fn extrude_z(t: Tree, zmin: TreeFloat, zmax: TreeFloat) -> Self {
-        Self(unsafe { sys::extrude_z(t.0, zmin.0, zmax.0) })
+    pub fn extrude_z(
+        t: Tree,
+        zmin: impl Into<TreeFloat>,
+        zmax: impl Into<TreeFloat>,
+    ) -> Self {
+        Self(unsafe { sys::extrude_z(t.0, zmin.into().0, zmax.into().0) })
     }
 }
-
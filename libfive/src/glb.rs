@@ -0,0 +1,125 @@
+use crate::*;
+use std::io::{self, Write};
+
+/// `glTF` in ASCII, little endian.
+const MAGIC: u32 = 0x4654_6c67;
+const VERSION: u32 = 2;
+/// `JSON` in ASCII, little endian.
+const CHUNK_JSON: u32 = 0x4e4f_534a;
+/// `BIN\0` in ASCII, little endian.
+const CHUNK_BIN: u32 = 0x004e_4942;
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+/// Writes `mesh` as a binary [`glTF`](https://www.khronos.org/gltf/) (GLB)
+/// file to `writer`.
+///
+/// The file holds a single scene with a single node referencing a single
+/// mesh. The mesh has one triangle primitive with `POSITION` and `NORMAL`
+/// attributes and `u32` indices. Normals are computed with
+/// [`TriangleMesh::vertex_normals()`].
+pub(crate) fn write_glb<T: Point3>(
+    mesh: &TriangleMesh<T>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    if mesh.positions.is_empty() || mesh.triangles.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "glTF accessors can not be empty",
+        ));
+    }
+
+    let positions = mesh
+        .positions
+        .iter()
+        .map(|point| [point.x(), point.y(), point.z()])
+        .collect::<Vec<_>>();
+    let normals = mesh.vertex_normals();
+
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for position in &positions {
+        for ((lower, upper), &value) in
+            min.iter_mut().zip(max.iter_mut()).zip(position)
+        {
+            *lower = lower.min(value);
+            *upper = upper.max(value);
+        }
+    }
+
+    let mut bin = Vec::new();
+    for value in positions.iter().chain(&normals).flatten() {
+        bin.extend_from_slice(&value.to_le_bytes());
+    }
+    for index in mesh.triangles.iter().flatten() {
+        bin.extend_from_slice(&index.to_le_bytes());
+    }
+
+    let vertex_count = positions.len();
+    let attribute_length = 12 * vertex_count;
+    let json = format!(
+        concat!(
+            r#"{{"asset":{{"version":"2.0","generator":"libfive-rs"}},"#,
+            r#""scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}],"#,
+            r#""meshes":[{{"primitives":[{{"attributes":"#,
+            r#"{{"POSITION":0,"NORMAL":1}},"indices":2}}]}}],"#,
+            r#""buffers":[{{"byteLength":{}}}],"#,
+            r#""bufferViews":["#,
+            r#"{{"buffer":0,"byteOffset":0,"byteLength":{},"target":{}}},"#,
+            r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}},"#,
+            r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}],"#,
+            r#""accessors":["#,
+            r#"{{"bufferView":0,"componentType":{},"count":{},"type":"VEC3","#,
+            r#""min":[{},{},{}],"max":[{},{},{}]}},"#,
+            r#"{{"bufferView":1,"componentType":{},"count":{},"type":"VEC3"}},"#,
+            r#"{{"bufferView":2,"componentType":{},"count":{},"type":"SCALAR"}}]}}"#,
+        ),
+        bin.len(),
+        attribute_length,
+        ARRAY_BUFFER,
+        attribute_length,
+        attribute_length,
+        ARRAY_BUFFER,
+        2 * attribute_length,
+        bin.len() - 2 * attribute_length,
+        ELEMENT_ARRAY_BUFFER,
+        FLOAT,
+        vertex_count,
+        min[0],
+        min[1],
+        min[2],
+        max[0],
+        max[1],
+        max[2],
+        FLOAT,
+        vertex_count,
+        UNSIGNED_INT,
+        3 * mesh.triangles.len(),
+    );
+
+    // Chunks must be 4-byte aligned. JSON is padded with spaces.
+    let mut json = json.into_bytes();
+    json.resize(json.len().next_multiple_of(4), b' ');
+    // All of the binary data consists of 4-byte values.
+    debug_assert_eq!(bin.len() % 4, 0);
+
+    let total_length = 12 + 8 + json.len() + 8 + bin.len();
+    let length = |length: usize| {
+        u32::try_from(length)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))
+    };
+
+    for value in [MAGIC, VERSION, length(total_length)?] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    for (chunk_type, data) in [(CHUNK_JSON, &json), (CHUNK_BIN, &bin)] {
+        writer.write_all(&length(data.len())?.to_le_bytes())?;
+        writer.write_all(&chunk_type.to_le_bytes())?;
+        writer.write_all(data)?;
+    }
+
+    Ok(())
+}
@@ -14,23 +14,23 @@
 //! ```ignore
 //! # use libfive::*;
 //! # fn example() -> Result<()> {
-//! let f_rep_shape = Tree::sphere(1.0.into(), TreeVec3::default())
+//! let f_rep_shape = Tree::sphere(1.0, TreeVec3::default())
 //!     .difference_multi(vec![
-//!         Tree::sphere(0.6.into(), TreeVec3::default()),
+//!         Tree::sphere(0.6, TreeVec3::default()),
 //!         Tree::cylinder_z(
-//!             0.6.into(),
-//!             2.0.into(),
+//!             0.6,
+//!             2.0,
 //!             TreeVec3::new(0.0, 0.0, -1.0),
 //!         ),
 //!         Tree::cylinder_z(
-//!             0.6.into(),
-//!             2.0.into(),
+//!             0.6,
+//!             2.0,
 //!             TreeVec3::new(0.0, 0.0, -1.0),
 //!         )
 //!         .reflect_xz(),
 //!         Tree::cylinder_z(
-//!             0.6.into(),
-//!             2.0.into(),
+//!             0.6,
+//!             2.0,
 //!             TreeVec3::new(0.0, 0.0, -1.0),
 //!         )
 //!         .reflect_yz(),
@@ -65,17 +65,37 @@
 //!   default-features = false
 //!   ```
 //!
+//! * `rayon` -- Export many trees in parallel with [`export_all_stl()`] on
+//!   [`rayon`](https://crates.io/crates/rayon)'s thread pool.
+//!
+//! * `image` -- Save [`Bitmap`]s as PNG files via the
+//!   [`image`](https://crates.io/crates/image) crate.
+//!
+//! * `gltf` -- Export meshes as binary [glTF](https://www.khronos.org/gltf/)
+//!   files with [`Tree::write_glb()`].
+//!
 //! * `packed_opcodes` -- Tightly pack opcodes. This breaks compatibility with
 //!   older saved f-rep files.
 //!
 //!   See [`Tree::save()`](Tree::save)/[`load()`](Tree::load).
 use core::{
     ffi::c_void,
+    fmt,
+    hash::{Hash, Hasher},
     ops::{Add, Div, Mul, Neg, Rem, Sub},
-    ptr, result, slice,
+    result, slice,
 };
 use libfive_sys as sys;
-use std::{ffi::CString, path::Path};
+use std::{
+    ffi::{CStr, CString},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+};
 use derive_more::{Display, Error, From};
 
 #[cfg(feature = "ahash")]
@@ -83,11 +103,46 @@ type HashMap<K, V> = ahash::AHashMap<K, V>;
 #[cfg(not(feature = "ahash"))]
 type HashMap<K, V> = std::collections::HashMap<K, V>;
 
+mod batch;
+use batch::BatchEvaluator;
+mod cache;
+pub use cache::MeshCache;
+mod contour;
+mod dxf;
+#[cfg(feature = "gltf")]
+mod glb;
+pub use contour::{
+    classify_contours, contour_length, contour_signed_area, ContourNode,
+};
+pub use dxf::write_dxf;
+mod expr;
+mod mesh;
+pub use mesh::MeshFormat;
+mod noise;
+mod obj;
+mod parametric;
+pub use parametric::Parametric;
+mod ply;
+mod shared;
+pub use shared::SharedTree;
+mod stl;
+pub use stl::StlFormat;
+mod svg;
+pub use svg::{FillRule, SvgStyle};
+mod temp;
+use temp::TempFile;
+
 #[cfg(feature = "stdlib")]
 mod stdlib;
 #[cfg(feature = "stdlib")]
 pub use stdlib::*;
 
+/// Whether the crate was built with the `packed_opcodes` feature.
+///
+/// Files [saved](Tree::save) by builds with packed opcodes are not
+/// compatible with those saved by builds without and vice versa.
+pub const PACKED_OPCODES: bool = cfg!(feature = "packed_opcodes");
+
 /// A specialized [`Result`] type for `libfive` operations.
 ///
 /// This type is broadly used across `libvive` for any operation which may
@@ -118,6 +173,36 @@ pub enum Error {
     FileReadFailed,
     /// The queried tree is not a constant.
     TreeIsNotConstant,
+    /// The tree contains variables, which the operation does not support.
+    TreeHasVariables,
+    /// The shape is not bounded, e.g. a half-space.
+    UnboundedShape,
+    /// The operation produced no geometry.
+    EmptyResult,
+    /// The resolution is not a finite number greater than zero.
+    InvalidResolution,
+    /// The region is inverted, has zero extent along an axis or is not
+    /// finite.
+    InvalidRegion,
+    /// The path contains a NUL byte.
+    InvalidPath,
+    /// The text contains a NUL byte.
+    InvalidText,
+    /// The operation was cancelled.
+    Cancelled,
+    /// The file was [saved](Tree::save) by an incompatible build.
+    #[display(
+        fmt = "IncompatibleFormat (version {}, packed opcodes: {})",
+        version,
+        packed_opcodes
+    )]
+    #[from(ignore)]
+    IncompatibleFormat {
+        /// Version of the file's header.
+        version: u32,
+        /// Whether the file was saved with [`PACKED_OPCODES`].
+        packed_opcodes: bool,
+    },
 }
 
 /// Trait to aid with using arbitrary 2D point types on a [`Contour`].
@@ -135,6 +220,38 @@ pub trait Point3 {
     fn z(&self) -> f32;
 }
 
+impl Point2 for [f32; 2] {
+    fn new(x: f32, y: f32) -> Self {
+        [x, y]
+    }
+
+    fn x(&self) -> f32 {
+        self[0]
+    }
+
+    fn y(&self) -> f32 {
+        self[1]
+    }
+}
+
+impl Point3 for [f32; 3] {
+    fn new(x: f32, y: f32, z: f32) -> Self {
+        [x, y, z]
+    }
+
+    fn x(&self) -> f32 {
+        self[0]
+    }
+
+    fn y(&self) -> f32 {
+        self[1]
+    }
+
+    fn z(&self) -> f32 {
+        self[2]
+    }
+}
+
 /// Series of 2D or 3D points forming a
 /// [polygonal chain](https://en.wikipedia.org/wiki/Polygonal_chain).
 pub type Contour<T> = Vec<T>;
@@ -142,6 +259,9 @@ pub type Contour<T> = Vec<T>;
 /// Bitmap representing occupancy in a slice of a [`Tree`].
 ///
 /// It contains `width()` * `height()` pixels, in row-major order.
+///
+/// Row `0` is at the lower `y` bound of the rendered region. Image files are
+/// written with the upper `y` bound at the top.
 pub struct Bitmap(*mut sys::libfive_pixels);
 
 impl Bitmap {
@@ -174,7 +294,26 @@ impl Bitmap {
     /// Returns the value of the pixel `x`, `y`.
     pub fn pixel(&self, x: u32, y: u32) -> bool {
         assert!(x < self.width() && y < self.height());
-        self.as_slice()[(y * self.height() + x) as usize]
+        self.as_slice()[(y * self.width() + x) as usize]
+    }
+
+    /// Returns a mutable reference to the pixel `x`, `y`.
+    pub fn pixel_mut(&mut self, x: u32, y: u32) -> &mut bool {
+        assert!(x < self.width() && y < self.height());
+        let width = self.width();
+        &mut self.as_slice_mut()[(y * width + x) as usize]
+    }
+
+    /// Returns an iterator over all pixels as `(x, y, value)` tuples, in
+    /// row-major order.
+    pub fn iter_pixels(&self) -> impl Iterator<Item = (u32, u32, bool)> + '_ {
+        let width = self.width();
+        self.as_slice()
+            .iter()
+            .enumerate()
+            .map(move |(index, &value)| {
+                (index as u32 % width, index as u32 / width, value)
+            })
     }
 
     /// Returns the width of the bitmap.
@@ -186,11 +325,116 @@ impl Bitmap {
     pub fn height(&self) -> u32 {
         unsafe { self.0.as_ref() }.unwrap().height
     }
+
+    /// Saves the bitmap to `path` in binary
+    /// [`PBM`](https://en.wikipedia.org/wiki/Netpbm#PBM_example) format.
+    ///
+    /// Occupied pixels are black.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FileWriteFailed`] if the file could not be written.
+    pub fn save_pbm(&self, path: impl AsRef<Path>) -> Result<()> {
+        let (width, height) = (self.width(), self.height());
+        let pixels = self.as_slice();
+
+        std::fs::File::create(path)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                write!(writer, "P4\n{} {}\n", width, height)?;
+
+                // Rows are padded to whole bytes.
+                let mut row = vec![0u8; width.div_ceil(8) as usize];
+                for y in (0..height as usize).rev() {
+                    row.fill(0);
+                    for (x, &pixel) in pixels
+                        [y * width as usize..(y + 1) * width as usize]
+                        .iter()
+                        .enumerate()
+                    {
+                        if pixel {
+                            row[x / 8] |= 0x80 >> (x % 8);
+                        }
+                    }
+                    writer.write_all(&row)?;
+                }
+
+                writer.flush()
+            })
+            .map_err(|_| Error::FileWriteFailed)
+    }
+
+    /// Saves the bitmap to `path` in
+    /// [`PNG`](https://en.wikipedia.org/wiki/PNG) format.
+    ///
+    /// Occupied pixels are white.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FileWriteFailed`] if the file could not be written.
+    #[cfg(feature = "image")]
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.to_image()
+            .save_with_format(path, image::ImageFormat::Png)
+            .map_err(|_| Error::FileWriteFailed)
+    }
+
+    /// Converts the bitmap into an [`image::GrayImage`].
+    ///
+    /// Occupied pixels are `255`, empty ones `0`.
+    ///
+    /// Images are y-down. I.e. the image's first row is the bitmap's last
+    /// row, at the upper `y` bound of the rendered region. This way the
+    /// image looks the same as the slice viewed from above.
+    #[cfg(feature = "image")]
+    pub fn to_image(&self) -> image::GrayImage {
+        let height = self.height();
+
+        image::GrayImage::from_fn(self.width(), height, |x, y| {
+            image::Luma([if self.pixel(x, height - 1 - y) {
+                255
+            } else {
+                0
+            }])
+        })
+    }
 }
 
 impl Drop for Bitmap {
     fn drop(&mut self) {
-        unsafe { sys::libfive_pixels_delete(&mut self.0 as *mut _ as _) };
+        unsafe { sys::libfive_pixels_delete(self.0) };
+    }
+}
+
+// The pixel buffer is owned exclusively by the `Bitmap`.
+unsafe impl Send for Bitmap {}
+
+/// Dense 3D grid of occupancy samples of a [`Tree`].
+///
+/// It contains `dimensions()[0]` × `dimensions()[1]` × `dimensions()[2]`
+/// voxels, with `x` varying fastest, then `y`, then `z`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoxelGrid {
+    dimensions: [u32; 3],
+    voxels: Vec<bool>,
+}
+
+impl VoxelGrid {
+    /// Returns the number of voxels along each axis.
+    pub fn dimensions(&self) -> [u32; 3] {
+        self.dimensions
+    }
+
+    /// Returns `true` if the voxel `x`, `y`, `z` is inside the shape.
+    pub fn at(&self, x: u32, y: u32, z: u32) -> bool {
+        let [width, height, depth] = self.dimensions;
+        assert!(x < width && y < height && z < depth);
+        self.voxels[((z * height + y) * width + x) as usize]
+    }
+
+    /// Returns the voxels as a flat `[bool]` slice.
+    pub fn as_slice(&self) -> &[bool] {
+        &self.voxels
     }
 }
 
@@ -205,6 +449,68 @@ pub struct TriangleMesh<T: Point3> {
     pub triangles: Vec<[u32; 3]>,
 }
 
+impl<T: Point3> TriangleMesh<T> {
+    /// Returns an iterator over the `triangles`, each resolved to references
+    /// to its three corner `positions`.
+    ///
+    /// Meshes produced by `libfive` only reference existing positions.
+    /// Triangles with an index out of range can hence only stem from a mesh
+    /// modified by hand. They are skipped.
+    pub fn triangles_with_positions(&self) -> impl Iterator<Item = [&T; 3]> {
+        self.triangles.iter().filter_map(|triangle| {
+            let [a, b, c] =
+                triangle.map(|index| self.positions.get(index as usize));
+            Some([a?, b?, c?])
+        })
+    }
+
+    /// Returns a unit normal for each of the `positions`.
+    ///
+    /// A vertex's normal is the average of the normals of the triangles
+    /// sharing it, weighted by their area. Counter-clockwise triangles face
+    /// towards the viewer, which is outwards for meshes of a [`Tree`].
+    ///
+    /// Vertices without any non-degenerate triangle get `[0, 0, 1]`.
+    pub fn vertex_normals(&self) -> Vec<[f32; 3]> {
+        let mut normals = vec![[0.0f32; 3]; self.positions.len()];
+
+        for triangle in &self.triangles {
+            let [a, b, c] = triangle.map(|index| {
+                let point = &self.positions[index as usize];
+                [point.x(), point.y(), point.z()]
+            });
+            let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+            let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+            // Its length is twice the triangle's area.
+            let normal = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+
+            for &index in triangle {
+                for (sum, component) in
+                    normals[index as usize].iter_mut().zip(normal)
+                {
+                    *sum += component;
+                }
+            }
+        }
+
+        normals
+            .into_iter()
+            .map(|normal| {
+                let length = normal.iter().map(|c| c * c).sum::<f32>().sqrt();
+                if length > 0.0 {
+                    normal.map(|c| c / length)
+                } else {
+                    [0.0, 0.0, 1.0]
+                }
+            })
+            .collect()
+    }
+}
+
 /// Flat triangle mesh.
 ///
 /// The `positions` list has layout `[x0, y0, z0, x1, y1, z1, ...]`.
@@ -229,12 +535,57 @@ impl<T: Point3> From<TriangleMesh<T>> for FlatTriangleMesh {
     }
 }
 
+impl<T: Point3> From<FlatTriangleMesh> for TriangleMesh<T> {
+    /// # Panics
+    ///
+    /// If the length of `positions` or `triangles` is not a multiple of
+    /// three or if a triangle references a vertex that does not exist.
+    fn from(mesh: FlatTriangleMesh) -> TriangleMesh<T> {
+        mesh.to_triangle_mesh()
+            .unwrap_or_else(|error| panic!("FlatTriangleMesh {}", error))
+    }
+}
+
+/// Options for [`Tree::to_triangle_mesh_with()`] and
+/// [`Tree::mesh_streaming()`].
+pub struct MeshOptions {
+    /// See [common arguments](Tree#common-arguments).
+    pub resolution: f32,
+    /// Set this to `true`, e.g. from another thread or from `progress`, to
+    /// cancel meshing.
+    pub cancel: Arc<AtomicBool>,
+    /// Called with the meshing progress, from `0.0` to `1.0`, while
+    /// `libfive` meshes. It may be called from one of `libfive`'s threads.
+    /// Meshing is cancelled if it panics.
+    pub progress: Option<Box<dyn Fn(f32) + Send + Sync>>,
+}
+
+impl MeshOptions {
+    /// Creates options for meshing at `resolution` that are not cancelled
+    /// and do not report progress.
+    pub fn new(resolution: f32) -> Self {
+        Self {
+            resolution,
+            cancel: Arc::new(AtomicBool::new(false)),
+            progress: None,
+        }
+    }
+}
+
 /// Set of variables to parameterize a [`Tree`].
+///
+/// Adding or removing variables never affects an existing [`Evaluator`]. It
+/// only sees the changes once it is [updated](Evaluator::update) with the
+/// set.
+///
+/// A [clone](Clone) holds the same variables, not new ones, but with its own
+/// values. Trees built from the variables of the original can thus be
+/// evaluated with the values of either.
+#[derive(Clone)]
 pub struct Variables {
     map: HashMap<String, usize>,
     variables: Vec<*const c_void>,
     values: Vec<f32>,
-    sys_variables: sys::libfive_vars,
 }
 
 impl Default for Variables {
@@ -250,11 +601,6 @@ impl Variables {
             map: HashMap::new(),
             variables: Vec::new(),
             values: Vec::new(),
-            sys_variables: sys::libfive_vars {
-                vars: ptr::null(),
-                values: ptr::null_mut(),
-                size: 0,
-            },
         }
     }
 
@@ -275,15 +621,42 @@ impl Variables {
             self.map.insert(name, self.variables.len());
             self.variables.push(id);
             self.values.push(value);
-            // Update struct.
-            self.sys_variables.vars = self.variables.as_ptr() as *const _ as _;
-            self.sys_variables.values = self.values.as_ptr() as *const _ as _;
-            self.sys_variables.size = self.variables.len().try_into().unwrap();
 
             Ok(Tree(tree))
         }
     }
 
+    /// Removes the variable `name` from the set.
+    ///
+    /// Trees containing the variable keep it. An [`Evaluator`] created or
+    /// [updated](Evaluator::update) with the set afterwards no longer sets
+    /// its value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::VariableNotFound`] if the variable does not exist in
+    /// the set.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        let index = self.map.remove(name).ok_or(Error::VariableNotFound)?;
+
+        self.variables.remove(index);
+        self.values.remove(index);
+        for other in self.map.values_mut() {
+            if index < *other {
+                *other -= 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes all variables from the set.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.variables.clear();
+        self.values.clear();
+    }
+
     /// Sets the variable `name` to `value`.
     ///
     /// # Errors
@@ -298,58 +671,220 @@ impl Variables {
             Err(Error::VariableNotFound)
         }
     }
+
+    /// Returns the current value of the variable `name` or [`None`] if the
+    /// variable does not exist in the set.
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.map.get(name).map(|&index| self.values[index])
+    }
+
+    /// Returns `true` if the variable `name` exists in the set.
+    pub fn contains(&self, name: &str) -> bool {
+        self.map.contains_key(name)
+    }
+
+    /// Returns the number of variables in the set.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the set contains no variables.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over the `(name, value)` pairs of the set.
+    ///
+    /// The iteration order is arbitrary.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> + '_ {
+        self.map
+            .iter()
+            .map(|(name, &index)| (name.as_str(), self.values[index]))
+    }
+
+    /// Returns the set in the form `libfive` takes it.
+    ///
+    /// The result points into the `variables` and `values` buffers. It is
+    /// built on each call rather than stored as the buffers move whenever
+    /// [`add()`](Variables::add) outgrows them. `libfive` copies the set
+    /// during the calls taking it and keeps no pointers to it. Hence an
+    /// [`Evaluator`] outliving a reallocation is fine.
+    fn sys_variables(&self) -> sys::libfive_vars {
+        sys::libfive_vars {
+            vars: self.variables.as_ptr() as *const _ as _,
+            values: self.values.as_ptr() as *const _ as _,
+            size: self.variables.len().try_into().unwrap(),
+        }
+    }
+
+    /// Returns the [id](Tree::id) and value of each variable.
+    fn values_by_id(&self) -> impl Iterator<Item = (usize, f32)> + '_ {
+        self.variables
+            .iter()
+            .map(|&id| id as usize)
+            .zip(self.values.iter().copied())
+    }
 }
 
-impl Drop for Variables {
-    fn drop(&mut self) {
-        unsafe {
-            sys::libfive_vars_delete(&mut self.sys_variables as *mut _ as _)
-        };
+impl fmt::Debug for Variables {
+    /// Prints the names and values of the variables in the order they were
+    /// added.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = vec![""; self.values.len()];
+        for (name, &index) in &self.map {
+            names[index] = name;
+        }
+
+        f.debug_map()
+            .entries(names.into_iter().zip(&self.values))
+            .finish()
     }
 }
 
+// The buffers `sys_variables()` points to are owned by the `variables` and
+// `values` `Vec`s. Handing them to `libfive_vars_delete()` would free them a
+// second time so there is no `Drop` impl for `Variables`.
+
 /// Helper for controlling evaluation of [`Variables`] on a [`Tree`].
-pub struct Evaluator(sys::libfive_evaluator);
+///
+/// An `Evaluator` can not be cloned as `libfive`'s C API has no call to copy
+/// one. Create another one from the same tree and variables instead.
+pub struct Evaluator {
+    evaluator: sys::libfive_evaluator,
+    /// Evaluates points. `libfive`'s evaluator only meshes.
+    batch: BatchEvaluator,
+    tree: Tree,
+    /// Current value of each variable, by [id](Tree::id).
+    values: HashMap<usize, f32>,
+}
+
+impl fmt::Debug for Evaluator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Evaluator").finish_non_exhaustive()
+    }
+}
 
 impl Evaluator {
     pub fn new(tree: &Tree, variables: &Variables) -> Self {
-        Self(unsafe {
-            sys::libfive_tree_evaluator(tree.0, variables.sys_variables)
-        })
+        Self {
+            evaluator: unsafe {
+                sys::libfive_tree_evaluator(tree.0, variables.sys_variables())
+            },
+            batch: BatchEvaluator::new(tree, variables),
+            tree: tree.clone(),
+            values: variables.values_by_id().collect(),
+        }
     }
 
-    pub fn update(&mut self, variables: &Variables) -> Result<()> {
-        if unsafe {
-            sys::libfive_evaluator_update_vars(self.0, variables.sys_variables)
-        } {
-            Err(Error::VariablesCouldNotBeUpdated)
-        } else {
-            Ok(())
+    /// Sets the values of the evaluator's variables to those in
+    /// `variables`.
+    ///
+    /// Returns whether any value changed. Variables the evaluator was not
+    /// created with are ignored.
+    pub fn update(&mut self, variables: &Variables) -> Result<bool> {
+        self.batch.update(variables);
+        for (id, value) in variables.values_by_id() {
+            if let Some(current) = self.values.get_mut(&id) {
+                *current = value;
+            }
         }
+
+        // `libfive` returns `true` if the values changed, not on failure.
+        Ok(unsafe {
+            sys::libfive_evaluator_update_vars(
+                self.evaluator,
+                variables.sys_variables(),
+            )
+        })
+    }
+
+    /// Evaluates the tree at each of `points`, with the current values of
+    /// the variables.
+    ///
+    /// `libfive`'s C API has no call to evaluate an evaluator at a point.
+    /// The points are evaluated by a second evaluator `libfive-sys` adds,
+    /// in batches, like in [`Tree::eval_many()`]. It is kept in sync by
+    /// [`update()`](Evaluator::update).
+    pub fn eval_many(&mut self, points: &[[f32; 3]]) -> Vec<f32> {
+        self.batch.eval(points)
     }
 
     /// Computes a mesh and saves it to `path` in
     /// [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)) format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidPath`] if `path` contains a NUL byte and
+    /// [`Error::FileWriteFailed`] if the file could not be written.
     pub fn write_stl(
         &self,
         path: impl AsRef<Path>,
         region: &Region3,
     ) -> Result<()> {
-        let path = c_string_from_path(path);
+        let path = c_string_from_path(path)?;
 
         if unsafe {
-            sys::libfive_evaluator_save_mesh(self.0, region.0, path.as_ptr())
+            sys::libfive_evaluator_save_mesh(
+                self.evaluator,
+                region.0,
+                path.as_ptr(),
+            )
         } {
             Ok(())
         } else {
             Err(Error::FileWriteFailed)
         }
     }
+
+    /// Computes a mesh of `region` and returns it as a [`TriangleMesh`].
+    ///
+    /// `libfive`'s C API has no call to render an evaluator's mesh into
+    /// memory. The mesh is therefore written to a temporary
+    /// [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)) file with
+    /// [`write_stl()`](Evaluator::write_stl) and read back from there.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FileWriteFailed`] if the temporary file could not be
+    /// written and [`Error::FileReadFailed`] if it could not be read back.
+    pub fn to_triangle_mesh<T: Point3>(
+        &self,
+        region: &Region3,
+    ) -> Result<TriangleMesh<T>> {
+        let temp = TempFile::new("stl").map_err(|_| Error::FileWriteFailed)?;
+        self.write_stl(temp.path(), region)?;
+
+        std::fs::File::open(temp.path())
+            .and_then(|file| {
+                stl::read_binary_stl(std::io::BufReader::new(file))
+            })
+            .map_err(|_| Error::FileReadFailed)
+    }
+
+    /// Renders a 2D slice of `region` at the given `z` height to a set of 2D
+    /// contours.
+    ///
+    /// `libfive`'s C API has no call to slice an evaluator. The current
+    /// values of the variables are therefore substituted into the tree,
+    /// which is then sliced with [`Tree::to_contour_2d()`].
+    ///
+    /// Returns [`None`] where [`Tree::to_contour_2d()`] does or if the tree
+    /// contains oracles.
+    pub fn to_contour_2d<T: Point2>(
+        &self,
+        region: Region2,
+        z: f32,
+        resolution: f32,
+    ) -> Option<Vec<Contour<T>>> {
+        expr::Expr::from_tree(&self.tree)?
+            .to_folded_tree(&self.values)
+            .to_contour_2d(region, z, resolution)
+    }
 }
 
 impl Drop for Evaluator {
     fn drop(&mut self) {
-        unsafe { sys::libfive_evaluator_delete(self.0) };
+        unsafe { sys::libfive_evaluator_delete(self.evaluator) };
     }
 }
 
@@ -370,6 +905,80 @@ impl Region2 {
             },
         })
     }
+
+    /// Creates a region of the given `size`, centered at `center`.
+    pub fn from_center_size(center: [f32; 2], size: [f32; 2]) -> Self {
+        Self::new(
+            center[0] - 0.5 * size[0],
+            center[0] + 0.5 * size[0],
+            center[1] - 0.5 * size[1],
+            center[1] + 0.5 * size[1],
+        )
+    }
+
+    /// Creates a square region extending `half_extent` from `center` along
+    /// each axis.
+    pub fn square(center: [f32; 2], half_extent: f32) -> Self {
+        Self::from_center_size(center, [2.0 * half_extent; 2])
+    }
+
+    /// Returns the lower corner of the region.
+    pub fn min(&self) -> [f32; 2] {
+        [self.0.X.lower, self.0.Y.lower]
+    }
+
+    /// Returns the upper corner of the region.
+    pub fn max(&self) -> [f32; 2] {
+        [self.0.X.upper, self.0.Y.upper]
+    }
+
+    /// Returns the center of the region.
+    pub fn center(&self) -> [f32; 2] {
+        let (min, max) = (self.min(), self.max());
+        [0.5 * (min[0] + max[0]), 0.5 * (min[1] + max[1])]
+    }
+
+    /// Returns the extent of the region along each axis.
+    pub fn size(&self) -> [f32; 2] {
+        let (min, max) = (self.min(), self.max());
+        [max[0] - min[0], max[1] - min[1]]
+    }
+
+    /// Checks that the region can be rendered.
+    ///
+    /// See [`Region3::validate()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRegion`] if a bound is not finite or a lower
+    /// bound is not less than the upper one.
+    pub fn validate(&self) -> Result<()> {
+        validate_intervals(&[self.0.X, self.0.Y])
+    }
+
+    /// Returns the bounding region of `self` and `other`.
+    pub fn union(&self, other: &Region2) -> Region2 {
+        Self(sys::libfive_region2 {
+            X: interval_hull(self.0.X, other.0.X),
+            Y: interval_hull(self.0.Y, other.0.Y),
+        })
+    }
+
+    /// Returns the region covered by both `self` and `other`.
+    ///
+    /// Returns [`None`] if they are disjoint or only touch.
+    pub fn intersection(&self, other: &Region2) -> Option<Region2> {
+        Some(Self(sys::libfive_region2 {
+            X: interval_intersection(self.0.X, other.0.X)?,
+            Y: interval_intersection(self.0.Y, other.0.Y)?,
+        }))
+    }
+
+    /// Returns `true` if `point` lies inside the region or on its boundary.
+    pub fn contains_point(&self, point: [f32; 2]) -> bool {
+        interval_contains(self.0.X, point[0])
+            && interval_contains(self.0.Y, point[1])
+    }
 }
 
 /// 3D bounding region.
@@ -400,9 +1009,134 @@ impl Region3 {
             },
         })
     }
+
+    /// Creates a region of the given `size`, centered at `center`.
+    pub fn from_center_size(center: [f32; 3], size: [f32; 3]) -> Self {
+        Self::new(
+            center[0] - 0.5 * size[0],
+            center[0] + 0.5 * size[0],
+            center[1] - 0.5 * size[1],
+            center[1] + 0.5 * size[1],
+            center[2] - 0.5 * size[2],
+            center[2] + 0.5 * size[2],
+        )
+    }
+
+    /// Creates a cube-shaped region extending `half_extent` from `center`
+    /// along each axis.
+    pub fn cube(center: [f32; 3], half_extent: f32) -> Self {
+        Self::from_center_size(center, [2.0 * half_extent; 3])
+    }
+
+    /// Returns the lower corner of the region.
+    pub fn min(&self) -> [f32; 3] {
+        [self.0.X.lower, self.0.Y.lower, self.0.Z.lower]
+    }
+
+    /// Returns the upper corner of the region.
+    pub fn max(&self) -> [f32; 3] {
+        [self.0.X.upper, self.0.Y.upper, self.0.Z.upper]
+    }
+
+    /// Returns the center of the region.
+    pub fn center(&self) -> [f32; 3] {
+        let (min, max) = (self.min(), self.max());
+        [
+            0.5 * (min[0] + max[0]),
+            0.5 * (min[1] + max[1]),
+            0.5 * (min[2] + max[2]),
+        ]
+    }
+
+    /// Returns the extent of the region along each axis.
+    pub fn size(&self) -> [f32; 3] {
+        let (min, max) = (self.min(), self.max());
+        [max[0] - min[0], max[1] - min[1], max[2] - min[2]]
+    }
+
+    /// Checks that the region can be rendered.
+    ///
+    /// [`new()`](Region3::new) accepts any bounds. But an inverted region,
+    /// e.g. with `x_min > x_max`, or one with zero volume makes `libfive`
+    /// silently produce no geometry. Methods rendering a region hence call
+    /// this first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRegion`] if a bound is not finite or a lower
+    /// bound is not less than the upper one.
+    pub fn validate(&self) -> Result<()> {
+        validate_intervals(&[self.0.X, self.0.Y, self.0.Z])
+    }
+
+    /// Returns the bounding region of `self` and `other`.
+    ///
+    /// Use it e.g. to render several models placed side by side at once.
+    pub fn union(&self, other: &Region3) -> Region3 {
+        Self(sys::libfive_region3 {
+            X: interval_hull(self.0.X, other.0.X),
+            Y: interval_hull(self.0.Y, other.0.Y),
+            Z: interval_hull(self.0.Z, other.0.Z),
+        })
+    }
+
+    /// Returns the region covered by both `self` and `other`.
+    ///
+    /// Returns [`None`] if they are disjoint or only touch, i.e. if the
+    /// overlap would have no volume.
+    pub fn intersection(&self, other: &Region3) -> Option<Region3> {
+        Some(Self(sys::libfive_region3 {
+            X: interval_intersection(self.0.X, other.0.X)?,
+            Y: interval_intersection(self.0.Y, other.0.Y)?,
+            Z: interval_intersection(self.0.Z, other.0.Z)?,
+        }))
+    }
+
+    /// Returns `true` if `point` lies inside the region or on its boundary.
+    pub fn contains_point(&self, point: [f32; 3]) -> bool {
+        interval_contains(self.0.X, point[0])
+            && interval_contains(self.0.Y, point[1])
+            && interval_contains(self.0.Z, point[2])
+    }
+}
+
+/// Range of values a [`Tree`] takes on inside a region.
+///
+/// See [`Tree::eval_interval()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    pub lower: f32,
+    pub upper: f32,
+}
+
+/// Whether a region lies inside a shape.
+///
+/// See [`Tree::region_state()`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RegionState {
+    /// The region is entirely outside the shape.
+    Empty,
+    /// The region is entirely inside the shape.
+    Full,
+    /// The region may contain parts of the shape's surface.
+    Ambiguous,
+}
+
+impl From<Interval> for RegionState {
+    /// Classifies a region by the interval the tree evaluates to over it.
+    fn from(interval: Interval) -> Self {
+        if interval.lower > 0.0 {
+            RegionState::Empty
+        } else if interval.upper < 0.0 {
+            RegionState::Full
+        } else {
+            RegionState::Ambiguous
+        }
+    }
 }
 
 #[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[repr(i32)]
 enum Op {
     Invalid = 0,
@@ -441,6 +1175,8 @@ enum Op {
     NanFill = 27,
     Compare = 31,
 
+    // Oracles are user-defined nodes evaluated by callbacks. They can only
+    // be created through `libfive`'s C++ API. The C API has no call for it.
     Oracle = 32,
 }
 
@@ -454,7 +1190,8 @@ macro_rules! fn_unary {
 }
 
 macro_rules! fn_binary {
-    ($func_name:ident, $op_code:ident, $other:ident) => {
+    ($(#[$meta:meta])* $func_name:ident, $op_code:ident, $other:ident) => {
+        $(#[$meta])*
         #[inline]
         pub fn $func_name(self, $other: Self) -> Self {
             Self(unsafe {
@@ -481,9 +1218,11 @@ macro_rules! op_binary {
 /// # Core
 ///
 /// * [Constant][`TreeFloat::from::<f32>()`]
+/// * [Angles](#angles)
 /// * [Bases](#bases)
 /// * [Functions](#functions)
 /// * [Evaluation, import & export](#eval)
+/// * [Noise](#noise)
 ///
 /// # Standard Library
 ///
@@ -494,13 +1233,37 @@ macro_rules! op_binary {
 /// * [Constructive solid geometry](#csg)
 /// * [Transformations](#transforms)
 /// * [Text](#text)
-#[derive(Eq, PartialEq)]
-pub struct Tree(sys::libfive_tree);
-
-/// An alias for [`Tree`].
 ///
-/// Used to make the kind of sensible input more obvious for some operators.
-pub type TreeFloat = Tree;
+/// Angles, e.g. of `rotate_x()`, `rotate_y()` and `rotate_z()`, are in
+/// radians. Use [`radians()`](Tree::radians) to convert from degrees.
+///
+/// Their [`TreeFloat`] parameters take `impl Into<TreeFloat>`. Hence both
+/// trees and plain numbers can be passed. See
+/// [`difference_multi()`](Tree::difference_multi) for an example.
+///
+/// # Thread Safety
+///
+/// A `Tree` is a handle to an immutable graph of nodes inside `libfive`.
+/// The graph is reference-counted atomically and never modified after
+/// construction. Operations build new graphs that share the nodes of their
+/// inputs.
+///
+/// Hence a `Tree` is [`Send`] and [`Sync`]. It can be moved or
+/// [cloned](Tree::clone) into other threads and be evaluated or meshed from
+/// several threads at once.
+///
+/// # Identity
+///
+/// Two `Tree`s are equal if they refer to the same node, i.e. if their
+/// [`id()`](Tree::id)s match. In particular a [clone](Tree::clone) is equal
+/// to its original. Trees built separately are not equal, even if they
+/// describe the same expression.
+pub struct Tree(sys::libfive_tree);
+
+/// An alias for [`Tree`].
+///
+/// Used to make the kind of sensible input more obvious for some operators.
+pub type TreeFloat = Tree;
 
 /// # Constants <a name="constant"></a>
 impl From<f32> for Tree {
@@ -510,6 +1273,41 @@ impl From<f32> for Tree {
     }
 }
 
+/// # Angles <a name="angles"></a>
+///
+/// Angles taken by `libfive` are in radians.
+impl Tree {
+    /// Returns the constant `π`, half a turn in radians.
+    pub fn pi() -> TreeFloat {
+        core::f32::consts::PI.into()
+    }
+
+    /// Returns the constant `τ = 2π`, a full turn in radians.
+    pub fn tau() -> TreeFloat {
+        core::f32::consts::TAU.into()
+    }
+
+    /// Returns the angle `degrees` in radians, as a constant.
+    ///
+    /// ```
+    /// # use libfive::*;
+    /// // The `x` coordinate turned a quarter turn counter-clockwise around
+    /// // the `z` axis.
+    /// let turned =
+    ///     Tree::x().rotate_z(Tree::radians(90.0), TreeVec3::default());
+    ///
+    /// assert!((turned.eval([0.0, 1.0, 0.0]) - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn radians(degrees: f32) -> TreeFloat {
+        degrees.to_radians().into()
+    }
+
+    /// Returns the angle `radians` in degrees, as a constant.
+    pub fn degrees(radians: f32) -> TreeFloat {
+        radians.to_degrees().into()
+    }
+}
+
 /// # Bases <a name="bases"></a>
 impl Tree {
     #[inline]
@@ -557,7 +1355,218 @@ impl Tree {
     fn_binary!(nth_root, NthRoot, n);
     fn_binary!(rem, Mod, rhs);
     fn_binary!(nan_fill, NanFill, rhs);
-    fn_binary!(compare, Compare, rhs);
+    fn_binary!(
+        /// Returns `-1` where the value is less than `rhs`, `0` where both
+        /// are equal and `1` where it is greater.
+        ///
+        /// This is the building block for conditional fields. See
+        /// [`less_than()`](Tree::less_than),
+        /// [`greater_than()`](Tree::greater_than) and
+        /// [`step()`](Tree::step).
+        ///
+        /// ```
+        /// # use libfive::*;
+        /// let compared = Tree::x().compare(1.0.into());
+        ///
+        /// assert_eq!(compared.eval([0.5, 0.0, 0.0]), -1.0);
+        /// assert_eq!(compared.eval([1.0, 0.0, 0.0]), 0.0);
+        /// assert_eq!(compared.eval([1.5, 0.0, 0.0]), 1.0);
+        /// ```
+        compare,
+        Compare,
+        rhs
+    );
+
+    /// Returns the tree with the coordinates `x`, `y` and `z` replaced by the
+    /// given trees.
+    ///
+    /// This is how transformations are built: e.g. `tree.remap(Tree::x() -
+    /// 1.0.into(), Tree::y(), Tree::z())` moves the shape by `1` along `x`.
+    ///
+    /// `x`, `y` and `z` are expressions in the original coordinates. All
+    /// three are substituted at once, so e.g. swapping `x` and `y` just
+    /// passes `Tree::y(), Tree::x(), Tree::z()`. This is `libfive`'s
+    /// `remap-shape` resp. `remap_xyz`.
+    #[doc(alias = "remap_xyz")]
+    pub fn remap(self, x: Tree, y: Tree, z: Tree) -> Self {
+        Self(unsafe { sys::libfive_tree_remap(self.0, x.0, y.0, z.0) })
+    }
+
+    /// Returns the minimum of all `trees`.
+    ///
+    /// For signed distance fields this is the union of their shapes.
+    ///
+    /// Returns positive infinity if there are no `trees`.
+    pub fn min_all(trees: impl IntoIterator<Item = Tree>) -> Self {
+        trees
+            .into_iter()
+            .reduce(|a, b| a.min(b))
+            .unwrap_or_else(|| f32::INFINITY.into())
+    }
+
+    /// Returns the maximum of all `trees`.
+    ///
+    /// For signed distance fields this is the intersection of their shapes.
+    ///
+    /// Returns negative infinity if there are no `trees`.
+    pub fn max_all(trees: impl IntoIterator<Item = Tree>) -> Self {
+        trees
+            .into_iter()
+            .reduce(|a, b| a.max(b))
+            .unwrap_or_else(|| f32::NEG_INFINITY.into())
+    }
+
+    /// Linearly interpolates between `a` and `b`, i.e. computes
+    /// `a * (1 - t) + b * t`.
+    ///
+    /// Unlike the stdlib's `morph()`, which blends shapes,
+    /// this is plain arithmetic on field values. Use a
+    /// [variable](Variables) for `t` to animate the blend with an
+    /// [`Evaluator`].
+    pub fn mix(a: Tree, b: Tree, t: impl Into<TreeFloat>) -> Self {
+        let t = t.into();
+        a * (Tree::from(1.0) - t.clone()) + b * t
+    }
+
+    /// Restricts the value of the tree to the range `[lo, hi]`.
+    ///
+    /// Evaluates to `hi` wherever `lo` is greater than `hi`.
+    pub fn clamp(
+        self,
+        lo: impl Into<TreeFloat>,
+        hi: impl Into<TreeFloat>,
+    ) -> Self {
+        self.max(lo.into()).min(hi.into())
+    }
+
+    /// Returns `0` where `x` is less than `edge` and `1` elsewhere.
+    pub fn step(edge: impl Into<TreeFloat>, x: Tree) -> Self {
+        // `compare()` yields -1, 0 or 1.
+        (x.compare(edge.into()) + Tree::from(1.0)).min(1.0.into())
+    }
+
+    /// Returns `0` where `x` is less than `edge0`, `1` where it is greater
+    /// than `edge1` and a smooth Hermite interpolation in between.
+    ///
+    /// The interpolation is the cubic `t * t * (3 - 2 * t)` with `t` the
+    /// position of `x` in `[edge0, edge1]`, clamped to `[0, 1]`.
+    ///
+    /// If `edge0` equals `edge1` this degenerates to a [`step()`](Tree::step)
+    /// except at `x == edge0` itself. There `t` is `0 / 0` and the result is
+    /// NaN. Use [`step()`](Tree::step) in this case.
+    pub fn smoothstep(
+        edge0: impl Into<TreeFloat>,
+        edge1: impl Into<TreeFloat>,
+        x: Tree,
+    ) -> Self {
+        let (edge0, edge1) = (edge0.into(), edge1.into());
+        let t = ((x - edge0.clone()) / (edge1 - edge0)).clamp(0.0, 1.0);
+        t.clone() * t.clone() * (Tree::from(3.0) - Tree::from(2.0) * t)
+    }
+
+    /// Returns the fractional part of the value, `x - floor(x)`.
+    ///
+    /// The result is always in `[0, 1)`, also for negative values. E.g.
+    /// `fract(-1.25)` is `0.75`, like GLSL's `fract()` and unlike
+    /// [`f32::fract()`], which keeps the sign.
+    pub fn fract(&self) -> Self {
+        // `libfive`'s `mod` is never negative for a positive divisor.
+        self.clone().rem(1.0.into())
+    }
+
+    /// Rounds the value down to the next integer, towards negative infinity.
+    ///
+    /// E.g. `floor(-1.5)` is `-2`.
+    pub fn floor(&self) -> Self {
+        self.clone() - self.fract()
+    }
+
+    /// Rounds the value up to the next integer, towards positive infinity.
+    ///
+    /// E.g. `ceil(-1.5)` is `-1`.
+    pub fn ceil(&self) -> Self {
+        -self.neg().floor()
+    }
+
+    /// Rounds the value to the nearest integer.
+    ///
+    /// Halfway cases are rounded up, towards positive infinity. E.g.
+    /// `round(-1.5)` is `-1` and `round(0.5)` is `1`. This differs from
+    /// [`f32::round()`], which rounds them away from zero.
+    pub fn round(&self) -> Self {
+        (self.clone() + 0.5.into()).floor()
+    }
+
+    /// Returns `-1` where the value is negative, `1` where it is positive
+    /// and `0` where it is zero.
+    pub fn sign(self) -> Self {
+        self.compare(0.0.into())
+    }
+
+    /// Returns `1` where the value is less than `rhs` and `0` elsewhere,
+    /// including where both are equal.
+    ///
+    /// The result is a mask, e.g. for [`mix()`](Tree::mix), not a distance
+    /// field.
+    pub fn less_than(self, rhs: Tree) -> Self {
+        rhs.compare(self).max(0.0.into())
+    }
+
+    /// Returns `1` where the value is greater than `rhs` and `0` elsewhere,
+    /// including where both are equal.
+    ///
+    /// The result is a mask, e.g. for [`mix()`](Tree::mix), not a distance
+    /// field.
+    pub fn greater_than(self, rhs: Tree) -> Self {
+        self.compare(rhs).max(0.0.into())
+    }
+
+    /// Returns `if_true` where `mask` is greater than zero and `if_false`
+    /// elsewhere.
+    ///
+    /// This builds piecewise fields, e.g. a different material per region.
+    /// The result is [mixed](Tree::mix) with a
+    /// [`greater_than()`](Tree::greater_than) mask. Hence both branches are
+    /// always evaluated and a non-finite value in the branch not taken,
+    /// e.g. an infinity times `0`, still turns the result into NaN.
+    ///
+    /// The result is not a signed distance field, even if both branches
+    /// are. It jumps where `mask` changes sign.
+    ///
+    /// ```
+    /// # use libfive::*;
+    /// let field = Tree::select(Tree::x(), 2.0.into(), 3.0.into());
+    ///
+    /// assert_eq!(field.eval([1.0, 0.0, 0.0]), 2.0);
+    /// assert_eq!(field.eval([-1.0, 0.0, 0.0]), 3.0);
+    /// ```
+    pub fn select(mask: Tree, if_true: Tree, if_false: Tree) -> Self {
+        Tree::mix(if_false, if_true, mask.greater_than(0.0.into()))
+    }
+
+    /// Returns the magnitude of the tree's value with the sign of `other`.
+    ///
+    /// Where `other` is zero the magnitude is returned unchanged, i.e. zero
+    /// counts as positive. Unlike [`sign()`](Tree::sign) this never
+    /// collapses the value to zero.
+    pub fn copysign(self, other: Tree) -> Self {
+        self.abs()
+            * (Tree::step(0.0, other) * Tree::from(2.0) - Tree::from(1.0))
+    }
+
+    /// Divides the tree by `divisor`, evaluating to `fallback` where the
+    /// quotient is NaN.
+    ///
+    /// This guards e.g. normalizing by a length that may be zero:
+    /// `x / sqrt(x² + y²)` is `0 / 0` at the origin. Note that dividing a
+    /// non-zero value by zero yields an infinity, not NaN. It is kept.
+    pub fn safe_div(
+        self,
+        divisor: Tree,
+        fallback: impl Into<TreeFloat>,
+    ) -> Self {
+        (self / divisor).nan_fill(fallback.into())
+    }
 
     /// Checks if the tree is a variable.
     pub fn is_variable(&self) -> bool {
@@ -584,6 +1593,68 @@ impl Tree {
             Err(Error::TreeIsNotConstant)
         }
     }
+
+    /// Returns the unique id of the tree's root node.
+    ///
+    /// [Clones](Tree::clone) share the id of their original. The id is only
+    /// stable while at least one handle to the node is alive.
+    pub fn id(&self) -> usize {
+        unsafe { sys::libfive_tree_id(self.0) as usize }
+    }
+
+    /// Returns the expression the tree represents as an
+    /// [S-expression](https://en.wikipedia.org/wiki/S-expression).
+    ///
+    /// E.g. `(+ (square x) (square y))`. This is also what the
+    /// [`Display`](fmt::Display) implementation prints.
+    ///
+    /// Shared subexpressions are printed in full wherever they occur. The
+    /// string can hence be much larger than the tree for deeply nested
+    /// expressions.
+    pub fn to_sexpr(&self) -> String {
+        expr::sexpr(self)
+    }
+
+    /// Returns the number of unique nodes in the tree.
+    ///
+    /// The tree is a DAG. Nodes that are shared, e.g. through
+    /// [clones](Tree::clone), are counted once. Structurally identical
+    /// subexpressions that were built separately are distinct nodes.
+    ///
+    /// Each node is visited once, so this is linear in the number of
+    /// nodes.
+    ///
+    /// Returns `None` if the tree contains oracles.
+    pub fn node_count(&self) -> Option<usize> {
+        expr::Expr::from_tree(self).map(|expr| expr.node_count())
+    }
+
+    /// Returns the number of nodes on the longest path from the root of the
+    /// tree to one of its leaves.
+    ///
+    /// Like [`node_count()`](Tree::node_count) this visits each node once.
+    ///
+    /// Returns `None` if the tree contains oracles.
+    pub fn depth(&self) -> Option<usize> {
+        expr::Expr::from_tree(self).map(|expr| expr.depth())
+    }
+
+    /// Returns the tree with constant subexpressions folded.
+    ///
+    /// Operations whose operands are all constant are replaced by their
+    /// value. It is computed on [`f32`]s, like `libfive` evaluates the
+    /// operation. But `libfive`'s math functions may round the last bit
+    /// differently than Rust's. Hence the result evaluates to the same
+    /// values as the original up to that rounding.
+    ///
+    /// `libfive` has no call for this. The tree is rebuilt node by node.
+    /// Trees containing oracles are returned as is.
+    pub fn optimized(&self) -> Self {
+        expr::Expr::from_tree(self).map_or_else(
+            || self.clone(),
+            |expr| expr.to_folded_tree(&HashMap::default()),
+        )
+    }
 }
 
 /// # Evaluation, Import & Export <a name="eval"></a>
@@ -594,6 +1665,18 @@ impl Tree {
 ///   quadtree/octree. For clean lines/triangles, it should be near-cubical.
 ///   But this is not a hard requirement.
 ///
+///   Its bounds must be finite and each lower bound must be less than the
+///   upper one. Methods rendering a region reject anything else with
+///   [`Error::InvalidRegion`], see [`Region3::validate()`].
+///
+/// * `path` -- The file to write to or read from. Paths are handed to
+///   `libfive` as a C string. Hence they must not contain NUL bytes.
+///
+///   On Windows the path is passed on as the UTF-8 encoding of its
+///   characters. `libfive` opens files with the narrow-character C/C++ APIs
+///   which interpret it in the active code page. Paths containing non-ASCII
+///   characters may therefore fail to open unless that code page is UTF-8.
+///
 /// * `resolution` -- The resolution used for meshing. Make this larger to get a
 ///   higher-resolution model.
 ///
@@ -602,9 +1685,283 @@ impl Tree {
 ///
 ///   For methods generating 3D data another way to think of resolution is as
 ///   the number of subdivision, per unit length, on each axis.
+///
+///   It must be a finite number greater than zero. Anything else is rejected
+///   as it would make `libfive` produce degenerate results or not terminate.
 impl Tree {
+    /// Evaluates the tree at `point`.
+    ///
+    /// Negative values are inside the shape, positive ones outside.
+    ///
+    /// Every call sets up a new `libfive` evaluator. Prefer
+    /// [`eval_many()`](Tree::eval_many) for more than a few points.
+    pub fn eval(&self, point: [f32; 3]) -> f32 {
+        unsafe {
+            sys::libfive_tree_eval_f(
+                self.0,
+                sys::libfive_vec3 {
+                    x: point[0],
+                    y: point[1],
+                    z: point[2],
+                },
+            )
+        }
+    }
+
+    /// Returns the gradient of the tree at `point`.
+    ///
+    /// For a distance field this is the surface normal, pointing outwards.
+    pub fn gradient(&self, point: [f32; 3]) -> [f32; 3] {
+        let gradient = unsafe {
+            sys::libfive_tree_eval_d(
+                self.0,
+                sys::libfive_vec3 {
+                    x: point[0],
+                    y: point[1],
+                    z: point[2],
+                },
+            )
+        };
+        [gradient.x, gradient.y, gradient.z]
+    }
+
+    /// Evaluates the tree over all of `region` using interval arithmetic.
+    ///
+    /// Every value the tree takes on inside `region` lies within the result.
+    /// Interval arithmetic is conservative though: the result may be wider
+    /// than the actual range. It gets tighter as `region` gets smaller.
+    pub fn eval_interval(&self, region: &Region3) -> Interval {
+        let interval = unsafe { sys::libfive_tree_eval_r(self.0, region.0) };
+
+        Interval {
+            lower: interval.lower,
+            upper: interval.upper,
+        }
+    }
+
+    /// Returns whether `region` is definitely outside, definitely inside or
+    /// possibly on the surface of the shape.
+    ///
+    /// This is the building block of octree traversals, e.g. for culling.
+    /// As [`eval_interval()`](Tree::eval_interval) is conservative a region
+    /// may be reported as [`Ambiguous`](RegionState::Ambiguous) even though
+    /// it does not touch the surface. But [`Empty`](RegionState::Empty) and
+    /// [`Full`](RegionState::Full) are always correct.
+    pub fn region_state(&self, region: &Region3) -> RegionState {
+        RegionState::from(self.eval_interval(region))
+    }
+
+    /// Moves `point` onto the surface of the shape, i.e. the zero isosurface
+    /// of the tree.
+    ///
+    /// Performs up to `iterations` Newton steps
+    /// `p -= f(p) * ∇f(p) / |∇f(p)|²`. For a distance field a single step
+    /// lands on the surface if the closest point is unique. Otherwise each
+    /// step gets closer. It stops early once `f(p)` is zero.
+    ///
+    /// Near creases and corners the gradient jumps. The steps may then
+    /// oscillate between the adjacent faces and the result need not be the
+    /// closest surface point. Where the gradient vanishes or is undefined,
+    /// e.g. at the center of a sphere, the point is returned as is.
+    pub fn project_to_surface(
+        &self,
+        mut point: [f32; 3],
+        iterations: u32,
+    ) -> [f32; 3] {
+        for _ in 0..iterations {
+            let value = self.eval(point);
+            let gradient = self.gradient(point);
+            let length_squared = gradient.iter().map(|g| g * g).sum::<f32>();
+
+            if value == 0.0 || !length_squared.is_normal() {
+                break;
+            }
+
+            for (coordinate, gradient) in point.iter_mut().zip(gradient) {
+                *coordinate -= value * gradient / length_squared;
+            }
+        }
+
+        point
+    }
+
+    /// Evaluates the tree at each of `points`.
+    ///
+    /// Unlike [`eval()`](Tree::eval) a single evaluator is set up for all
+    /// points. They are evaluated in batches, as many at once as `libfive`'s
+    /// array evaluator takes.
+    pub fn eval_many(&self, points: &[[f32; 3]]) -> Vec<f32> {
+        BatchEvaluator::new(self, &Variables::new()).eval(points)
+    }
+
+    /// Samples `region` on a grid of `resolution` voxels per axis into a
+    /// [`VoxelGrid`].
+    ///
+    /// Each voxel is sampled at its center. It is occupied if the tree is
+    /// negative there. All voxels are evaluated with a single evaluator, in
+    /// batches.
+    ///
+    /// # Errors
+    ///
+    /// See [`to_sdf_grid()`](Tree::to_sdf_grid).
+    pub fn to_voxel_grid(
+        &self,
+        region: &Region3,
+        resolution: [u32; 3],
+    ) -> Result<VoxelGrid> {
+        Ok(VoxelGrid {
+            dimensions: resolution,
+            voxels: self
+                .to_sdf_grid(region, resolution)?
+                .into_iter()
+                .map(|value| value < 0.0)
+                .collect(),
+        })
+    }
+
+    /// Samples the tree's values on a grid of `resolution` voxels per axis
+    /// covering `region`.
+    ///
+    /// Each voxel is sampled at its center. The values are in row-major
+    /// order with `x` varying fastest, then `y`, then `z`. I.e. the value of
+    /// voxel `x`, `y`, `z` is at index
+    /// `(z * resolution[1] + y) * resolution[0] + x`.
+    ///
+    /// The values are only distances if the tree is a signed distance
+    /// field. All voxels are evaluated with a single evaluator, in batches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRegion`] if the `region` is empty, inverted
+    /// or not finite and [`Error::InvalidResolution`] if a `resolution` is
+    /// zero or there are too many voxels to hold their centers in memory.
+    pub fn to_sdf_grid(
+        &self,
+        region: &Region3,
+        resolution: [u32; 3],
+    ) -> Result<Vec<f32>> {
+        region.validate()?;
+        validate_sample_count(&resolution)?;
+
+        Ok(self.eval_many(&voxel_centers(region, resolution)))
+    }
+
+    /// Renders a top-down height map of `region` with `resolution` pixels
+    /// along `x` and `y`.
+    ///
+    /// For each pixel a ray is marched down from the upper `z` bound of the
+    /// `region`, through the pixel's center. The step size is the smaller of
+    /// the pixel's width and height. The first crossing into the shape is
+    /// then refined by bisection. [`None`] means the ray hit nothing.
+    ///
+    /// All rays are marched at once. A single evaluator evaluates the rays
+    /// that did not hit anything yet at each step and then all bisections
+    /// of a round, in batches.
+    ///
+    /// The pixels are in row-major order with row `0` at the lower `y` bound
+    /// of the `region`, like in a [`Bitmap`].
+    ///
+    /// Features thinner than a step may be missed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRegion`] if the `region` is empty, inverted
+    /// or not finite and [`Error::InvalidResolution`] if a `resolution` is
+    /// zero or there are too many pixels to hold them in memory.
+    pub fn to_heightmap(
+        &self,
+        region: &Region3,
+        resolution: [u32; 2],
+    ) -> Result<Vec<Option<f32>>> {
+        const BISECTIONS: u32 = 16;
+
+        region.validate()?;
+        validate_sample_count(&resolution)?;
+
+        let (min, max, size) = (region.min(), region.max(), region.size());
+        let step = (size[0] / resolution[0] as f32)
+            .min(size[1] / resolution[1] as f32);
+        let steps = (size[2] / step).ceil().max(1.0) as u32;
+        let step = size[2] / steps as f32;
+
+        let mut evaluator = BatchEvaluator::new(self, &Variables::new());
+        let pixels = voxel_centers(region, [resolution[0], resolution[1], 1]);
+        let mut heights = vec![None; pixels.len()];
+
+        // Pixels whose ray did not hit the shape yet.
+        let mut misses = (0..pixels.len()).collect::<Vec<_>>();
+        // Pixel and the heights the surface is between, for rays that hit.
+        let mut hits = Vec::new();
+
+        for i in 0..=steps {
+            if misses.is_empty() {
+                break;
+            }
+
+            let z = max[2] - i as f32 * step;
+            let values = evaluator.eval(
+                &misses
+                    .iter()
+                    .map(|&pixel| [pixels[pixel][0], pixels[pixel][1], z])
+                    .collect::<Vec<_>>(),
+            );
+
+            let mut values = values.into_iter();
+            misses.retain(|&pixel| {
+                if values.next().is_some_and(|value| value < 0.0) {
+                    if i == 0 {
+                        heights[pixel] = Some(max[2]);
+                    } else {
+                        hits.push((pixel, z + step, z.max(min[2])));
+                    }
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        for _ in 0..BISECTIONS {
+            if hits.is_empty() {
+                break;
+            }
+
+            let values = evaluator.eval(
+                &hits
+                    .iter()
+                    .map(|&(pixel, upper, lower)| {
+                        let [x, y, _] = pixels[pixel];
+                        [x, y, 0.5 * (upper + lower)]
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+            for ((_, upper, lower), value) in hits.iter_mut().zip(values) {
+                let center = 0.5 * (*upper + *lower);
+                if value < 0.0 {
+                    *lower = center;
+                } else {
+                    *upper = center;
+                }
+            }
+        }
+
+        for (pixel, upper, lower) in hits {
+            heights[pixel] = Some(0.5 * (upper + lower));
+        }
+
+        Ok(heights)
+    }
+
     /// Renders a 2D slice of `region` at the given `z` height into a
     /// [`Bitmap`].
+    ///
+    /// A pixel is `true` where the shape occupies it, i.e. where the tree
+    /// evaluates to a negative value, and `false` outside.
+    ///
+    /// Nothing is checked. See
+    /// [`to_bitmap_checked()`](Tree::to_bitmap_checked) for a variant that
+    /// reports invalid arguments and empty slices.
     #[inline]
     pub fn to_bitmap(
         &self,
@@ -617,67 +1974,378 @@ impl Tree {
         })
     }
 
-    /// Renders `region` to a [`TriangleMesh`].
-    pub fn to_triangle_mesh<T: Point3>(
+    /// Renders a 2D slice of `region` at the given `z` height into a
+    /// [`Bitmap`], like [`to_bitmap()`](Tree::to_bitmap).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero, the errors of [`Region2::validate()`] and
+    /// [`Error::EmptyResult`] if `libfive` produced no bitmap, a bitmap
+    /// without pixels or one where no pixel is occupied.
+    pub fn to_bitmap_checked(
         &self,
-        region: &Region3,
+        region: &Region2,
+        z: f32,
         resolution: f32,
-    ) -> Option<TriangleMesh<T>> {
-        match unsafe {
-            sys::libfive_tree_render_mesh(self.0, region.0, resolution).as_mut()
-        } {
-            Some(raw_mesh) => {
-                let mesh = TriangleMesh::<T> {
-                    positions: (0..raw_mesh.vert_count)
-                        .map(|index| {
-                            let vertex =
-                                &unsafe { *raw_mesh.verts.add(index as _) };
-                            T::new(vertex.x, vertex.y, vertex.z)
-                        })
-                        .collect(),
-                    triangles: (0..raw_mesh.tri_count)
-                        .map(|index| {
-                            let triangle =
-                                &unsafe { *raw_mesh.tris.add(index as _) };
-                            [triangle.a, triangle.b, triangle.c]
-                        })
-                        .collect(),
-                };
+    ) -> Result<Bitmap> {
+        validate_resolution(resolution)?;
+        region.validate()?;
 
-                unsafe {
-                    sys::libfive_mesh_delete(raw_mesh as *mut _ as _);
-                }
+        let pixels = unsafe {
+            sys::libfive_tree_render_pixels(self.0, region.0, z, resolution)
+        };
+        if pixels.is_null() {
+            return Err(Error::EmptyResult);
+        }
 
-                Some(mesh)
-            }
-            None => None,
+        let bitmap = Bitmap(pixels);
+        // `as_slice()` needs a pixel buffer, which may be missing if empty.
+        if bitmap.width() != 0
+            && bitmap.height() != 0
+            && bitmap.as_slice().iter().any(|&occupied| occupied)
+        {
+            Ok(bitmap)
+        } else {
+            Err(Error::EmptyResult)
         }
     }
 
-    /// Renders a 2D slice of `region` at the given `z` height to a set of 2D
-    /// contours.
-    pub fn to_contour_2d<T: Point2>(
+    /// Renders `region` to a [`TriangleMesh`] on a new thread.
+    ///
+    /// The tree is [cloned](Tree::clone) and the clone moved to the thread.
+    /// I.e. `self` can be dropped or modified while meshing runs.
+    ///
+    /// # Errors
+    ///
+    /// The thread returns [`Error::InvalidResolution`] if `resolution` is not
+    /// a finite number greater than zero and [`Error::EmptyResult`] if
+    /// `libfive` produced no mesh.
+    pub fn spawn_mesh<T: Point3 + Send + 'static>(
         &self,
-        region: Region2,
-        z: f32,
+        region: &Region3,
         resolution: f32,
-    ) -> Option<Vec<Contour<T>>> {
-        match unsafe {
-            sys::libfive_tree_render_slice(self.0, region.0, z, resolution)
-                .as_mut()
-        } {
-            Some(raw_contours) => {
-                let contours = (0..raw_contours.count)
-                    .map(|index| {
-                        let contour =
-                            unsafe { raw_contours.cs.add(index as _).as_ref() }
-                                .unwrap();
-                        (0..contour.count)
-                            .map(|index| {
-                                let point = unsafe {
-                                    contour.pts.add(index as _).as_ref()
-                                }
-                                .unwrap();
+    ) -> JoinHandle<Result<TriangleMesh<T>>> {
+        let (tree, region) = (self.clone(), *region);
+
+        std::thread::spawn(move || {
+            validate_resolution(resolution)?;
+            region.validate()?;
+            tree.to_triangle_mesh(&region, resolution)
+                .ok_or(Error::EmptyResult)
+        })
+    }
+
+    /// Renders `region` to a [`TriangleMesh`], with the option to cancel and
+    /// to observe progress.
+    ///
+    /// `libfive`'s C API has no hooks into a running meshing operation.
+    /// This uses a meshing call `libfive-sys` adds instead. It calls
+    /// `progress` with `0.0` before meshing starts and then as `libfive`
+    /// reports it. Each time, the `cancel` flag is checked and meshing
+    /// stops once it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Cancelled`] if the `cancel` flag was set,
+    /// [`Error::InvalidResolution`] if the `resolution` is not a finite
+    /// number greater than zero and [`Error::EmptyResult`] if `libfive`
+    /// produced no mesh.
+    pub fn to_triangle_mesh_with<T: Point3>(
+        &self,
+        region: &Region3,
+        options: &MeshOptions,
+    ) -> Result<TriangleMesh<T>> {
+        let raw_mesh = self.render_mesh_with(region, options)?;
+        let mesh = triangle_mesh_from_raw(raw_mesh);
+
+        unsafe {
+            sys::libfive_mesh_delete(raw_mesh as *mut _ as _);
+        }
+
+        Ok(mesh)
+    }
+
+    /// Renders `region` and calls `f` with the corner positions of each
+    /// triangle, in the order `libfive` produced them.
+    ///
+    /// Use this to write large meshes straight to their destination. No
+    /// [`TriangleMesh`] is built. Only `libfive`'s own indexed mesh is held
+    /// in memory while the triangles are passed on.
+    ///
+    /// `libfive` only returns complete meshes. The first triangle is
+    /// therefore passed once meshing finished. Progress and cancelling work
+    /// like in [`to_triangle_mesh_with()`](Tree::to_triangle_mesh_with).
+    /// The `cancel` flag of `options` is also checked before each triangle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Cancelled`] if the `cancel` flag was set,
+    /// [`Error::InvalidResolution`] if the `resolution` is not a finite
+    /// number greater than zero and [`Error::EmptyResult`] if `libfive`
+    /// produced no mesh. Triangles passed to `f` before cancelling are not
+    /// taken back.
+    pub fn mesh_streaming(
+        &self,
+        region: &Region3,
+        options: &MeshOptions,
+        mut f: impl FnMut([[f32; 3]; 3]),
+    ) -> Result<()> {
+        let cancelled = || options.cancel.load(Ordering::Relaxed);
+
+        let raw_mesh = self.render_mesh_with(region, options)?;
+
+        let vertex = |index: u32| {
+            let vertex = &unsafe { *raw_mesh.verts.add(index as _) };
+            [vertex.x, vertex.y, vertex.z]
+        };
+        let result = (0..raw_mesh.tri_count).try_for_each(|index| {
+            if cancelled() {
+                return Err(Error::Cancelled);
+            }
+            let triangle = &unsafe { *raw_mesh.tris.add(index as _) };
+            f([triangle.a, triangle.b, triangle.c].map(vertex));
+            Ok(())
+        });
+
+        unsafe {
+            sys::libfive_mesh_delete(raw_mesh as *mut _ as _);
+        }
+
+        result
+    }
+
+    /// Renders `region` with the meshing call `libfive-sys` adds, which
+    /// reports progress and can be cancelled.
+    ///
+    /// Delete the mesh with `libfive_mesh_delete()`.
+    fn render_mesh_with(
+        &self,
+        region: &Region3,
+        options: &MeshOptions,
+    ) -> Result<&mut sys::libfive_mesh> {
+        validate_resolution(options.resolution)?;
+        region.validate()?;
+
+        let raw_mesh = unsafe {
+            sys::libfive_rs_tree_render_mesh(
+                self.0,
+                region.0,
+                options.resolution,
+                Some(mesh_progress),
+                options as *const MeshOptions as *mut c_void,
+            )
+            .as_mut()
+        };
+
+        match raw_mesh {
+            Some(raw_mesh) if 0 < raw_mesh.tri_count => Ok(raw_mesh),
+            Some(raw_mesh) => {
+                unsafe {
+                    sys::libfive_mesh_delete(raw_mesh as *mut _ as _);
+                }
+                Err(Error::EmptyResult)
+            }
+            None if options.cancel.load(Ordering::Relaxed) => {
+                Err(Error::Cancelled)
+            }
+            None => Err(Error::EmptyResult),
+        }
+    }
+
+    /// Renders 2D slices of `region` at each of the `z_values` into
+    /// [`Bitmap`]s.
+    ///
+    /// The slices are rendered in parallel. The result is in the order of
+    /// `z_values`.
+    pub fn to_slice_stack(
+        &self,
+        region: &Region2,
+        z_values: &[f32],
+        resolution: f32,
+    ) -> Vec<Bitmap> {
+        par_map(z_values, |&z| self.to_bitmap(region, z, resolution))
+    }
+
+    /// Computes 2D slices of `region` at each of the `z_values` and saves
+    /// them in [`SVG`](https://en.wikipedia.org/wiki/Scalable_Vector_Graphics)
+    /// format to `directory`.
+    ///
+    /// The slices are rendered in parallel. The files are named
+    /// `slice-0000.svg`, `slice-0001.svg`, etc., in the order of `z_values`.
+    /// Their paths are returned in the same order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidPath`] if `directory` contains a NUL byte.
+    pub fn write_svg_stack(
+        &self,
+        directory: impl AsRef<Path>,
+        region: &Region2,
+        z_values: &[f32],
+        resolution: f32,
+    ) -> Result<Vec<PathBuf>> {
+        let paths = (0..z_values.len())
+            .map(|index| {
+                directory.as_ref().join(format!("slice-{:04}.svg", index))
+            })
+            .collect::<Vec<_>>();
+        let slices = paths.iter().zip(z_values).collect::<Vec<_>>();
+
+        par_map(&slices, |&(path, &z)| {
+            self.write_svg(path, region, z, resolution)
+        })
+        .into_iter()
+        .collect::<Result<()>>()?;
+
+        Ok(paths)
+    }
+
+    /// Renders `region` to a [`TriangleMesh`].
+    ///
+    /// Returns [`None`] if `resolution` is invalid or `libfive` produced no
+    /// triangles, e.g. because the shape is empty or does not intersect
+    /// `region`.
+    pub fn to_triangle_mesh<T: Point3>(
+        &self,
+        region: &Region3,
+        resolution: f32,
+    ) -> Option<TriangleMesh<T>> {
+        validate_resolution(resolution).ok()?;
+        region.validate().ok()?;
+
+        match unsafe {
+            sys::libfive_tree_render_mesh(self.0, region.0, resolution).as_mut()
+        } {
+            Some(raw_mesh) => {
+                let mesh = triangle_mesh_from_raw::<T>(raw_mesh);
+
+                unsafe {
+                    sys::libfive_mesh_delete(raw_mesh as *mut _ as _);
+                }
+
+                (!mesh.triangles.is_empty()).then_some(mesh)
+            }
+            None => None,
+        }
+    }
+
+    /// Renders `region` to a [`TriangleMesh`] at a resolution fine enough
+    /// to capture features of `target_feature_size`.
+    ///
+    /// A feature is only guaranteed to show up if at least one sample lies
+    /// inside it. Hence the edge of the smallest meshing cell must be at
+    /// most half of `target_feature_size`. The resolution is
+    ///
+    /// `resolution = ceil(2 × extent / target_feature_size) / extent`
+    ///
+    /// with `extent` the region's largest size along any axis. I.e. a whole
+    /// number of cells spans the region and the smallest edge, `1 /
+    /// resolution`, is at most `target_feature_size / 2`.
+    ///
+    /// Meshing time and memory grow with the third power of the resolution.
+    /// Pass the size of the smallest feature that matters, not a smaller
+    /// one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `target_feature_size` is not
+    /// a finite number greater than zero, the errors of
+    /// [`Region3::validate()`] and [`Error::EmptyResult`] if `libfive`
+    /// produced no triangles.
+    pub fn to_triangle_mesh_adaptive<T: Point3>(
+        &self,
+        region: &Region3,
+        target_feature_size: f32,
+    ) -> Result<TriangleMesh<T>> {
+        validate_resolution(target_feature_size)?;
+        region.validate()?;
+
+        let extent = region.size().into_iter().fold(0.0, f32::max);
+        let resolution = (2.0 * extent / target_feature_size).ceil() / extent;
+
+        validate_resolution(resolution)?;
+        self.to_triangle_mesh(region, resolution)
+            .ok_or(Error::EmptyResult)
+    }
+
+    /// Renders `region` into `positions` and `triangles`, laid out like the
+    /// fields of a [`FlatTriangleMesh`].
+    ///
+    /// Both buffers are cleared first but keep their capacity. Re-meshing
+    /// into the same buffers, e.g. in an interactive loop, hence only
+    /// allocates when a mesh is larger than any before.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero and [`Error::EmptyResult`] if `libfive`
+    /// produced no triangles. The buffers are left empty in both cases.
+    pub fn mesh_into(
+        &self,
+        region: &Region3,
+        resolution: f32,
+        positions: &mut Vec<f32>,
+        triangles: &mut Vec<u32>,
+    ) -> Result<()> {
+        positions.clear();
+        triangles.clear();
+        validate_resolution(resolution)?;
+        region.validate()?;
+
+        let raw_mesh = unsafe {
+            sys::libfive_tree_render_mesh(self.0, region.0, resolution).as_mut()
+        }
+        .ok_or(Error::EmptyResult)?;
+
+        positions.extend((0..raw_mesh.vert_count).flat_map(|index| {
+            let vertex = &unsafe { *raw_mesh.verts.add(index as _) };
+            [vertex.x, vertex.y, vertex.z]
+        }));
+        triangles.extend((0..raw_mesh.tri_count).flat_map(|index| {
+            let triangle = &unsafe { *raw_mesh.tris.add(index as _) };
+            [triangle.a, triangle.b, triangle.c]
+        }));
+
+        unsafe {
+            sys::libfive_mesh_delete(raw_mesh as *mut _ as _);
+        }
+
+        if triangles.is_empty() {
+            positions.clear();
+            return Err(Error::EmptyResult);
+        }
+
+        Ok(())
+    }
+
+    /// Renders a 2D slice of `region` at the given `z` height to a set of 2D
+    /// contours.
+    pub fn to_contour_2d<T: Point2>(
+        &self,
+        region: Region2,
+        z: f32,
+        resolution: f32,
+    ) -> Option<Vec<Contour<T>>> {
+        validate_resolution(resolution).ok()?;
+        region.validate().ok()?;
+
+        match unsafe {
+            sys::libfive_tree_render_slice(self.0, region.0, z, resolution)
+                .as_mut()
+        } {
+            Some(raw_contours) => {
+                let contours = (0..raw_contours.count)
+                    .map(|index| {
+                        let contour =
+                            unsafe { raw_contours.cs.add(index as _).as_ref() }
+                                .unwrap();
+                        (0..contour.count)
+                            .map(|index| {
+                                let point = unsafe {
+                                    contour.pts.add(index as _).as_ref()
+                                }
+                                .unwrap();
                                 T::new(point.x, point.y)
                             })
                             .collect()
@@ -701,9 +2369,12 @@ impl Tree {
         z: f32,
         resolution: f32,
     ) -> Option<Vec<Contour<T>>> {
+        validate_resolution(resolution).ok()?;
+        region.validate().ok()?;
+
         let raw_contours = unsafe {
             sys::libfive_tree_render_slice3(self.0, region.0, z, resolution)
-                .as_ref()
+                .as_mut()
         };
 
         if let Some(raw_contours) = raw_contours {
@@ -725,7 +2396,7 @@ impl Tree {
                 .collect();
 
             unsafe {
-                sys::libfive_contours_delete(&raw_contours as *const _ as _);
+                sys::libfive_contours3_delete(raw_contours as *mut _);
             }
 
             Some(contours)
@@ -737,40 +2408,37 @@ impl Tree {
     /// Computes a 2D slice of `region` at the given `z` height and saves it to
     /// `path` in [`SVG`](https://en.wikipedia.org/wiki/Scalable_Vector_Graphics)
     /// format.
+    ///
+    /// `libfive` writes a document without outlines for an empty slice. The
+    /// slice is therefore [contoured](Tree::to_contour_2d) first. This
+    /// renders it twice but is cheap compared to meshing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero, [`Error::InvalidPath`] if `path` contains a
+    /// NUL byte, [`Error::EmptyResult`] if the slice contains no outlines
+    /// and [`Error::FileWriteFailed`] if `libfive` could not write the file.
     pub fn write_svg(
         &self,
         path: impl AsRef<Path>,
         region: &Region2,
         z: f32,
         resolution: f32,
-    ) {
-        let path = c_string_from_path(path);
-
-        unsafe {
-            sys::libfive_tree_save_slice(
-                self.0,
-                region.0,
-                z,
-                resolution,
-                path.as_ptr(),
-            );
-        }
-    }
-
-    /// Computes a mesh of `region` and saves it to `path` in
-    /// [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)) format.
-    pub fn write_stl(
-        &self,
-        path: impl AsRef<Path>,
-        region: &Region3,
-        resolution: f32,
     ) -> Result<()> {
-        let path = c_string_from_path(path);
+        validate_resolution(resolution)?;
+        region.validate()?;
+        let path = c_string_from_path(path)?;
+
+        self.to_contour_2d::<[f32; 2]>(*region, z, resolution)
+            .filter(|contours| !contours.is_empty())
+            .ok_or(Error::EmptyResult)?;
 
         if unsafe {
-            sys::libfive_tree_save_mesh(
+            sys::libfive_tree_save_slice(
                 self.0,
                 region.0,
+                z,
                 resolution,
                 path.as_ptr(),
             )
@@ -781,163 +2449,3793 @@ impl Tree {
         }
     }
 
-    /// Serializes the tree to a file.
-    ///
-    /// <div class="warning">
+    /// Computes a 2D slice of `region` at the given `z` height and returns it
+    /// as an [`SVG`](https://en.wikipedia.org/wiki/Scalable_Vector_Graphics)
+    /// document.
     ///
-    /// The file format is not archival and may change without notice.
+    /// Unlike [`write_svg()`](Tree::write_svg) the SVG is generated on the
+    /// Rust side, from the contours of the slice. It follows the same
+    /// conventions: one unit is one SVG user unit and `y` is flipped so the
+    /// slice appears upright.
     ///
-    /// Saved files may fail to load with older versions of `libfive` if the
-    /// `packed_opcodes` feature is enabled.
+    /// # Errors
     ///
-    /// </div>
-    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
-        let path = c_string_from_path(path);
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero and [`Error::EmptyResult`] if `libfive`
+    /// produced no contours.
+    pub fn to_svg_string(
+        &self,
+        region: &Region2,
+        z: f32,
+        resolution: f32,
+    ) -> Result<String> {
+        validate_resolution(resolution)?;
+        region.validate()?;
+        let contours = self
+            .to_contour_2d::<[f32; 2]>(*region, z, resolution)
+            .filter(|contours| !contours.is_empty())
+            .ok_or(Error::EmptyResult)?;
 
-        if unsafe { sys::libfive_tree_save(self.0, path.as_ptr()) } {
-            Ok(())
-        } else {
-            Err(Error::FileWriteFailed)
-        }
+        let mut svg = Vec::new();
+        svg::write_svg(&contours, region, &SvgStyle::default(), &mut svg)
+            .map_err(|_| Error::FileWriteFailed)?;
+
+        Ok(String::from_utf8(svg).expect("SVG is ASCII"))
     }
 
-    /// Deserializes a tree from a file.
+    /// Computes a 2D slice of `region` at the given `z` height and saves it to
+    /// `path` in [`SVG`](https://en.wikipedia.org/wiki/Scalable_Vector_Graphics)
+    /// format, drawn with the given `style`.
     ///
-    /// <div class="warning">
+    /// The SVG is generated like with [`to_svg_string()`](Tree::to_svg_string).
+    /// Each outline is a single `<path>` together with the holes inside it.
+    /// Thus filling it leaves the holes empty.
     ///
-    /// Old files may fail to load if the `packed_opcodes` feature is enabled.
+    /// # Errors
     ///
-    /// </div>
-    pub fn load(&self, path: impl AsRef<Path>) -> Result<Tree> {
-        let path = c_string_from_path(path);
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero, [`Error::EmptyResult`] if `libfive`
+    /// produced no contours and [`Error::FileWriteFailed`] if the file could
+    /// not be written.
+    pub fn write_svg_styled(
+        &self,
+        path: impl AsRef<Path>,
+        region: &Region2,
+        z: f32,
+        resolution: f32,
+        style: &SvgStyle,
+    ) -> Result<()> {
+        validate_resolution(resolution)?;
+        region.validate()?;
+        let contours = self
+            .to_contour_2d::<[f32; 2]>(*region, z, resolution)
+            .filter(|contours| !contours.is_empty())
+            .ok_or(Error::EmptyResult)?;
 
-        match unsafe { sys::libfive_tree_load(path.as_ptr()).as_mut() } {
-            Some(tree) => Ok(Self(tree as _)),
-            None => Err(Error::FileReadFailed),
-        }
+        std::fs::File::create(path)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                svg::write_svg(&contours, region, style, &mut writer)?;
+                writer.flush()
+            })
+            .map_err(|_| Error::FileWriteFailed)
     }
-}
 
-impl Drop for Tree {
-    fn drop(&mut self) {
-        unsafe { sys::libfive_tree_delete(self.0) };
-    }
-}
+    /// Computes 2D slices of `region` at each of the `z_values` and saves
+    /// them as layers of a single
+    /// [`SVG`](https://en.wikipedia.org/wiki/Scalable_Vector_Graphics) file
+    /// to `path`.
+    ///
+    /// Each slice is a `<g>` group with a `data-z` attribute holding its
+    /// height. The groups are ordered by ascending height so upper slices are
+    /// drawn over lower ones. Outlines are colored by height, from blue for
+    /// the lowest to red for the highest slice.
+    ///
+    /// The slices are rendered in parallel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero, [`Error::EmptyResult`] if `libfive`
+    /// produced no contours for a slice and [`Error::FileWriteFailed`] if the
+    /// file could not be written.
+    pub fn write_svg_layers(
+        &self,
+        path: impl AsRef<Path>,
+        region: &Region2,
+        z_values: &[f32],
+        resolution: f32,
+    ) -> Result<()> {
+        validate_resolution(resolution)?;
+        region.validate()?;
+        let layers = par_map(z_values, |&z| {
+            self.to_contour_2d::<[f32; 2]>(*region, z, resolution)
+                .map(|contours| (z, contours))
+                .ok_or(Error::EmptyResult)
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
 
-op_binary!(add, Add);
-op_binary!(div, Div);
-op_binary!(mul, Mul);
-op_binary!(rem, Rem);
-op_binary!(sub, Sub);
+        std::fs::File::create(path)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                svg::write_svg_layers(&layers, region, &mut writer)?;
+                writer.flush()
+            })
+            .map_err(|_| Error::FileWriteFailed)
+    }
 
-impl Neg for Tree {
-    type Output = Tree;
+    /// Computes a 2D slice of `region` at the given `z` height and saves it to
+    /// `path` in [`DXF`](https://en.wikipedia.org/wiki/AutoCAD_DXF) format.
+    ///
+    /// See [`write_dxf()`](fn@write_dxf) for the contents of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero, [`Error::EmptyResult`] if `libfive`
+    /// produced no contours and [`Error::FileWriteFailed`] if the file could
+    /// not be written.
+    pub fn write_dxf(
+        &self,
+        path: impl AsRef<Path>,
+        region: &Region2,
+        z: f32,
+        resolution: f32,
+    ) -> Result<()> {
+        validate_resolution(resolution)?;
+        region.validate()?;
+        let contours = self
+            .to_contour_2d::<[f32; 2]>(*region, z, resolution)
+            .filter(|contours| !contours.is_empty())
+            .ok_or(Error::EmptyResult)?;
 
-    fn neg(self) -> Self::Output {
-        Self(unsafe { sys::libfive_tree_unary(Op::Neg as _, self.0) })
+        std::fs::File::create(path)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                dxf::write_dxf(&contours, &mut writer)?;
+                writer.flush()
+            })
+            .map_err(|_| Error::FileWriteFailed)
     }
-}
-
-fn c_string_from_path<P: AsRef<Path>>(path: P) -> CString {
-    CString::new(path.as_ref().as_os_str().as_encoded_bytes()).unwrap()
-}
 
-#[test]
+    /// Computes a mesh of `region` and saves it to `path` in
+    /// [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)) format.
+    ///
+    /// `libfive` would write a file without triangles for an empty mesh.
+    /// The mesh is therefore rendered first and written as binary STL, like
+    /// `libfive` does, once it is known to have triangles.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero, [`Error::EmptyResult`] if `libfive`
+    /// produced no triangles and [`Error::FileWriteFailed`] if the file
+    /// could not be written.
+    pub fn write_stl(
+        &self,
+        path: impl AsRef<Path>,
+        region: &Region3,
+        resolution: f32,
+    ) -> Result<()> {
+        self.write_stl_with_format(path, region, resolution, StlFormat::Binary)
+    }
+
+    /// Computes a mesh of `region` and saves it to `path` in
+    /// [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)) format with
+    /// the given encoding.
+    ///
+    /// The data is encoded on the Rust side. Hence both formats are
+    /// available regardless of the `libfive` version.
+    /// [`write_stl()`](Tree::write_stl) writes binary STL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero, [`Error::EmptyResult`] if `libfive`
+    /// produced no mesh and [`Error::FileWriteFailed`] if the file could not
+    /// be written.
+    pub fn write_stl_with_format(
+        &self,
+        path: impl AsRef<Path>,
+        region: &Region3,
+        resolution: f32,
+        format: StlFormat,
+    ) -> Result<()> {
+        validate_resolution(resolution)?;
+        region.validate()?;
+        let mesh = self
+            .to_triangle_mesh::<[f32; 3]>(region, resolution)
+            .ok_or(Error::EmptyResult)?;
+
+        std::fs::File::create(path)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                stl::write_stl(&mesh, &mut writer, format)?;
+                writer.flush()
+            })
+            .map_err(|_| Error::FileWriteFailed)
+    }
+
+    /// Computes a mesh of `region` and saves it to `path` as a binary
+    /// [`glTF`](https://www.khronos.org/gltf/) (GLB) file.
+    ///
+    /// The file holds a single mesh with positions, per-vertex normals from
+    /// [`TriangleMesh::vertex_normals()`] and triangle indices.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero, [`Error::EmptyResult`] if `libfive`
+    /// produced no triangles and [`Error::FileWriteFailed`] if the file could
+    /// not be written.
+    #[cfg(feature = "gltf")]
+    pub fn write_glb(
+        &self,
+        path: impl AsRef<Path>,
+        region: &Region3,
+        resolution: f32,
+    ) -> Result<()> {
+        validate_resolution(resolution)?;
+        region.validate()?;
+        let mesh = self
+            .to_triangle_mesh::<[f32; 3]>(region, resolution)
+            .ok_or(Error::EmptyResult)?;
+
+        std::fs::File::create(path)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                glb::write_glb(&mesh, &mut writer)?;
+                writer.flush()
+            })
+            .map_err(|_| Error::FileWriteFailed)
+    }
+
+    /// Computes a mesh of `region` and writes it to `writer` in binary
+    /// [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)) format.
+    ///
+    /// Like in [`write_stl()`](Tree::write_stl) the STL data is encoded on
+    /// the Rust side. Hence the `writer` can be anything, e.g. a network
+    /// socket.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`InvalidInput`](io::ErrorKind::InvalidInput)
+    /// if the mesh could not be computed or any error `writer` returns.
+    pub fn write_stl_to(
+        &self,
+        writer: impl Write,
+        region: &Region3,
+        resolution: f32,
+    ) -> io::Result<()> {
+        let mesh = self
+            .to_triangle_mesh::<[f32; 3]>(region, resolution)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    validate_resolution(resolution)
+                        .and(region.validate())
+                        .err()
+                        .unwrap_or(Error::EmptyResult),
+                )
+            })?;
+
+        stl::write_binary_stl(&mesh, writer)
+    }
+
+    /// Computes a mesh of `region` and returns it as binary
+    /// [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)) data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a finite
+    /// number greater than zero and [`Error::EmptyResult`] if `libfive`
+    /// produced no mesh.
+    pub fn to_stl_bytes(
+        &self,
+        region: &Region3,
+        resolution: f32,
+    ) -> Result<Vec<u8>> {
+        validate_resolution(resolution)?;
+        region.validate()?;
+        let mesh = self
+            .to_triangle_mesh::<[f32; 3]>(region, resolution)
+            .ok_or(Error::EmptyResult)?;
+
+        let mut bytes = Vec::new();
+        stl::write_binary_stl(&mesh, &mut bytes)
+            .map_err(|_| Error::FileWriteFailed)?;
+
+        Ok(bytes)
+    }
+
+    /// Computes a mesh of the shape's [bounds](Tree::bounds), padded by
+    /// `margin` on each side, and saves it to `path` in
+    /// [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)) format.
+    ///
+    /// The bounds are searched for inside `search`. They are refined until
+    /// they are tighter than `margin` or a voxel, whichever is smaller.
+    ///
+    /// Each refinement is a call to [`bounds()`](Tree::bounds) and costs up
+    /// to about `8⁶` interval evaluations. A `search` region that is close
+    /// to the shape's size needs fewer of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResolution`] if `resolution` is not a
+    /// positive, finite number and [`Error::InvalidRegion`] if `search` is
+    /// empty, inverted or not finite.
+    ///
+    /// Returns [`Error::UnboundedShape`] if the shape extends to the edge of
+    /// `search`, e.g. for a [`half_space()`](Tree::half_space).
+    ///
+    /// Returns [`Error::EmptyResult`] if no part of the shape is inside
+    /// `search`.
+    pub fn write_stl_auto(
+        &self,
+        path: impl AsRef<Path>,
+        search: &Region3,
+        resolution: f32,
+        margin: f32,
+    ) -> Result<()> {
+        validate_resolution(resolution)?;
+        let region = self.auto_region(search, resolution, margin)?;
+        self.write_stl(path, &region, resolution)
+    }
+
+    /// Returns the shape's bounds padded by `margin`.
+    ///
+    /// Each pass of [`bounds()`](Tree::bounds) shrinks the search region to
+    /// the shape plus at most one cell, 1/64th of the previous region, on
+    /// each side. Passes are repeated until a cell is smaller than `margin`
+    /// and a voxel at `resolution` or, for larger shapes, until the bounds
+    /// hardly shrink anymore. The cells then are about 1/62nd of the shape's
+    /// size.
+    fn auto_region(
+        &self,
+        search: &Region3,
+        resolution: f32,
+        margin: f32,
+    ) -> Result<Region3> {
+        const CELLS: f32 = 64.0;
+
+        search.validate()?;
+        let mut bounds = self.bounds(search).ok_or(Error::EmptyResult)?;
+
+        if (0..3).any(|axis| {
+            bounds.min()[axis] <= search.min()[axis]
+                || search.max()[axis] <= bounds.max()[axis]
+        }) {
+            return Err(Error::UnboundedShape);
+        }
+
+        let cell_size = |region: &Region3| {
+            region.size().into_iter().fold(0.0, f32::max) / CELLS
+        };
+        let target = if 0.0 < margin {
+            margin.min(1.0 / resolution)
+        } else {
+            1.0 / resolution
+        };
+
+        while target <= cell_size(&bounds) {
+            let refined = self.bounds(&bounds).ok_or(Error::EmptyResult)?;
+            let converged = 0.5 * cell_size(&bounds) < cell_size(&refined);
+            bounds = refined;
+            if converged {
+                break;
+            }
+        }
+
+        let pad = |interval: sys::libfive_interval| sys::libfive_interval {
+            lower: interval.lower - margin,
+            upper: interval.upper + margin,
+        };
+
+        Ok(Region3(sys::libfive_region3 {
+            X: pad(bounds.0.X),
+            Y: pad(bounds.0.Y),
+            Z: pad(bounds.0.Z),
+        }))
+    }
+
+    /// Serializes the tree to a file.
+    ///
+    /// The file is what `libfive` writes. `libfive` itself and
+    /// [libfive Studio](https://libfive.com/studio/) can read it. Use
+    /// [`save_versioned()`](Tree::save_versioned) to have builds of this
+    /// crate reject files they can not read with a descriptive error.
+    ///
+    /// <div class="warning">
+    ///
+    /// The file format is not archival and may change with any version of
+    /// `libfive`.
+    ///
+    /// </div>
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidPath`] if `path` contains a NUL byte and
+    /// [`Error::FileWriteFailed`] if the file could not be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = c_string_from_path(path)?;
+
+        if unsafe { sys::libfive_tree_save(self.0, path.as_ptr()) } {
+            Ok(())
+        } else {
+            Err(Error::FileWriteFailed)
+        }
+    }
+
+    /// Serializes the tree to a file with a header.
+    ///
+    /// The data [`save()`](Tree::save) writes is preceded by a small header.
+    /// It holds the header's version, whether the file was written with
+    /// [`PACKED_OPCODES`] and, for information, the version and git revision
+    /// of `libfive`. [`load_versioned()`](Tree::load_versioned) uses it to
+    /// reject files it can not read with a descriptive error.
+    ///
+    /// <div class="warning">
+    ///
+    /// Because of the header neither `libfive` itself nor
+    /// [libfive Studio](https://libfive.com/studio/) can read the files. Use
+    /// [`save()`](Tree::save) or [`save_scheme()`](Tree::save_scheme) to
+    /// exchange shapes with them.
+    ///
+    /// </div>
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidPath`] if `path` contains a NUL byte and
+    /// [`Error::FileWriteFailed`] if the file could not be written.
+    pub fn save_versioned(&self, path: impl AsRef<Path>) -> Result<()> {
+        c_string_from_path(&path)?;
+
+        // `libfive` can only save to a file. Its data is copied over.
+        let temp = TempFile::new("frep").map_err(|_| Error::FileWriteFailed)?;
+        self.save(temp.path())?;
+        let data =
+            std::fs::read(temp.path()).map_err(|_| Error::FileWriteFailed)?;
+        let libfive_version = libfive_version();
+
+        std::fs::File::create(path)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                writer.write_all(FREP_MAGIC)?;
+                writer.write_all(&FREP_VERSION.to_le_bytes())?;
+                writer.write_all(&[PACKED_OPCODES as u8])?;
+                writer.write_all(&[libfive_version.len() as u8])?;
+                writer.write_all(&libfive_version)?;
+                writer.write_all(&data)?;
+                writer.flush()
+            })
+            .map_err(|_| Error::FileWriteFailed)
+    }
+
+    /// Saves the tree to `path` as a Scheme expression that
+    /// [libfive Studio](https://libfive.com/studio/) and `libfive`'s Guile
+    /// bindings can read.
+    ///
+    /// The file holds a single `lambda-shape` of `x`, `y` and `z` around the
+    /// tree's [S-expression](Tree::to_sexpr). Studio renders it like any
+    /// other shape a top-level expression evaluates to. Operators are
+    /// spelled like in the bindings, e.g. `atan` instead of `atan2`.
+    ///
+    /// Unlike the binary [`save()`](Tree::save)
+    ///
+    /// * the file is meant to be read and edited by humans and can not be
+    ///   [loaded](Tree::load) again,
+    ///
+    /// * shared subexpressions are written in full wherever they occur.
+    ///   Files of deeply nested trees can hence be very large,
+    ///
+    /// * constants are written in decimal with the precision `libfive`
+    ///   prints them with and
+    ///
+    /// * variables are not supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TreeHasVariables`] if the tree contains variables
+    /// and [`Error::FileWriteFailed`] if the file could not be written.
+    pub fn save_scheme(&self, path: impl AsRef<Path>) -> Result<()> {
+        let scheme = expr::scheme(self).ok_or(Error::TreeHasVariables)?;
+
+        std::fs::write(
+            path,
+            format!(
+                ";; Written by libfive-rs.\n(lambda-shape (x y z)\n  {})\n",
+                scheme
+            ),
+        )
+        .map_err(|_| Error::FileWriteFailed)
+    }
+
+    /// Deserializes a tree from a file.
+    ///
+    /// The file must be one `libfive` wrote, e.g. with
+    /// [`save()`](Tree::save). Use [`load_versioned()`](Tree::load_versioned)
+    /// for files written by [`save_versioned()`](Tree::save_versioned).
+    ///
+    /// <div class="warning">
+    ///
+    /// Old files may fail to load if the `packed_opcodes` feature is enabled.
+    /// Check [`PACKED_OPCODES`] to find out whether it is.
+    ///
+    /// </div>
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidPath`] if `path` contains a NUL byte and
+    /// [`Error::FileReadFailed`] if the file could not be read, e.g. because
+    /// it was saved by a build that differs in [`PACKED_OPCODES`].
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<Tree> {
+        load_frep(&c_string_from_path(path)?)
+    }
+
+    /// Deserializes a tree from a file written by
+    /// [`save_versioned()`](Tree::save_versioned).
+    ///
+    /// The header is checked first. The version of `libfive` recorded in it
+    /// is not. Files written by another version of `libfive` are handed to
+    /// it and fail to load only if their data really is incompatible.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidPath`] if `path` contains a NUL byte,
+    /// [`Error::IncompatibleFormat`] if the file's header has an unknown
+    /// version or differs in [`PACKED_OPCODES`] and [`Error::FileReadFailed`]
+    /// if the file has no header or could not be read.
+    pub fn load_versioned(&self, path: impl AsRef<Path>) -> Result<Tree> {
+        c_string_from_path(&path)?;
+        let data = std::fs::read(path).map_err(|_| Error::FileReadFailed)?;
+
+        let header = data
+            .strip_prefix(FREP_MAGIC.as_slice())
+            .filter(|header| 5 <= header.len())
+            .ok_or(Error::FileReadFailed)?;
+
+        let version =
+            u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let packed_opcodes = header[4] != 0;
+        if version != FREP_VERSION || packed_opcodes != PACKED_OPCODES {
+            return Err(Error::IncompatibleFormat {
+                version,
+                packed_opcodes,
+            });
+        }
+
+        // Skip the version of `libfive` the file was saved with.
+        let Some((&length, header)) = header[5..].split_first() else {
+            return Err(Error::FileReadFailed);
+        };
+        let data =
+            header.get(length as usize..).ok_or(Error::FileReadFailed)?;
+
+        // `libfive` can only load from a file. The data is copied there.
+        let temp = TempFile::new("frep").map_err(|_| Error::FileReadFailed)?;
+        std::fs::write(temp.path(), data).map_err(|_| Error::FileReadFailed)?;
+
+        self.load(temp.path())
+    }
+
+    /// Computes the bounds of the shape inside `hint`.
+    ///
+    /// The `hint` is recursively subdivided into an octree. Cells that
+    /// interval arithmetic proves to be outside the shape are discarded. The
+    /// result is the bounding box of the remaining cells.
+    ///
+    /// Interval arithmetic is conservative. The bounds may therefore be
+    /// larger than the shape by up to 1/64th of the `hint`'s size along
+    /// each axis but they never clip it.
+    ///
+    /// All cells are evaluated with a single interval evaluator. Only cells
+    /// on the surface are subdivided. This takes up to about `8⁶`
+    /// evaluations but usually a small fraction of that.
+    ///
+    /// Returns [`None`] if the shape does not intersect `hint`.
+    pub fn bounds(&self, hint: &Region3) -> Option<Region3> {
+        const MAX_DEPTH: u32 = 6;
+
+        let mut evaluator = BatchEvaluator::new(self, &Variables::new());
+        let mut bounds: Option<Region3> = None;
+        let mut cells = vec![(hint.0, 0)];
+
+        while let Some((cell, depth)) = cells.pop() {
+            let state = evaluator.region_state(&Region3(cell));
+
+            if state == RegionState::Empty {
+                continue;
+            }
+
+            // Filled or ambiguous at the finest level.
+            if state == RegionState::Full || depth == MAX_DEPTH {
+                bounds = Some(match bounds {
+                    Some(bounds) => bounds.union(&Region3(cell)),
+                    None => Region3(cell),
+                });
+                continue;
+            }
+
+            for octant in 0..8 {
+                cells.push((
+                    sys::libfive_region3 {
+                        X: interval_half(cell.X, octant & 1 != 0),
+                        Y: interval_half(cell.Y, octant & 2 != 0),
+                        Z: interval_half(cell.Z, octant & 4 != 0),
+                    },
+                    depth + 1,
+                ));
+            }
+        }
+
+        bounds
+    }
+}
+
+/// Copies a mesh `libfive` rendered.
+fn triangle_mesh_from_raw<T: Point3>(
+    raw_mesh: &sys::libfive_mesh,
+) -> TriangleMesh<T> {
+    TriangleMesh {
+        positions: (0..raw_mesh.vert_count)
+            .map(|index| {
+                let vertex = &unsafe { *raw_mesh.verts.add(index as _) };
+                T::new(vertex.x, vertex.y, vertex.z)
+            })
+            .collect(),
+        triangles: (0..raw_mesh.tri_count)
+            .map(|index| {
+                let triangle = &unsafe { *raw_mesh.tris.add(index as _) };
+                [triangle.a, triangle.b, triangle.c]
+            })
+            .collect(),
+    }
+}
+
+/// Forwards meshing progress to the [`MeshOptions`] `data` points to.
+///
+/// Returns whether meshing should go on. A panic in the `progress` callback
+/// must not unwind into `libfive`. It cancels meshing instead.
+extern "C" fn mesh_progress(data: *mut c_void, progress: f32) -> bool {
+    let options = unsafe { &*(data as *const MeshOptions) };
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        if let Some(callback) = &options.progress {
+            callback(progress);
+        }
+    }));
+    if result.is_err() {
+        options.cancel.store(true, Ordering::Relaxed);
+    }
+
+    !options.cancel.load(Ordering::Relaxed)
+}
+
+/// Returns the centers of a grid of `resolution` voxels covering `region`,
+/// with `x` varying fastest, then `y`, then `z`.
+fn voxel_centers(region: &Region3, resolution: [u32; 3]) -> Vec<[f32; 3]> {
+    let (min, size) = (region.min(), region.size());
+    let coordinate = |axis: usize, index: u32| {
+        min[axis] + (index as f32 + 0.5) * size[axis] / resolution[axis] as f32
+    };
+
+    (0..resolution[2])
+        .flat_map(|z| {
+            (0..resolution[1]).flat_map(move |y| {
+                (0..resolution[0]).map(move |x| {
+                    [coordinate(0, x), coordinate(1, y), coordinate(2, z)]
+                })
+            })
+        })
+        .collect()
+}
+
+fn interval_hull(
+    a: sys::libfive_interval,
+    b: sys::libfive_interval,
+) -> sys::libfive_interval {
+    sys::libfive_interval {
+        lower: a.lower.min(b.lower),
+        upper: a.upper.max(b.upper),
+    }
+}
+
+/// Returns the overlap of `a` and `b` or [`None`] if it has no extent.
+fn interval_intersection(
+    a: sys::libfive_interval,
+    b: sys::libfive_interval,
+) -> Option<sys::libfive_interval> {
+    let interval = sys::libfive_interval {
+        lower: a.lower.max(b.lower),
+        upper: a.upper.min(b.upper),
+    };
+    (interval.lower < interval.upper).then_some(interval)
+}
+
+fn interval_contains(interval: sys::libfive_interval, value: f32) -> bool {
+    interval.lower <= value && value <= interval.upper
+}
+
+fn interval_half(
+    interval: sys::libfive_interval,
+    upper: bool,
+) -> sys::libfive_interval {
+    let center = 0.5 * (interval.lower + interval.upper);
+
+    if upper {
+        sys::libfive_interval {
+            lower: center,
+            upper: interval.upper,
+        }
+    } else {
+        sys::libfive_interval {
+            lower: interval.lower,
+            upper: center,
+        }
+    }
+}
+
+impl Clone for Tree {
+    /// Creates a new handle to the same tree.
+    ///
+    /// This is cheap. The tree's nodes are shared, not copied.
+    fn clone(&self) -> Self {
+        Self(unsafe { sys::libfive_tree_clone(self.0) })
+    }
+}
+
+impl Drop for Tree {
+    fn drop(&mut self) {
+        unsafe { sys::libfive_tree_delete(self.0) };
+    }
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_sexpr())
+    }
+}
+
+impl fmt::Debug for Tree {
+    /// Prints the tree's [`id()`](Tree::id) and the operation of its root
+    /// node, e.g. `Tree { id: 42, op: Add }`.
+    ///
+    /// This takes constant time, regardless of the size of the tree. Use
+    /// [`Display`](fmt::Display) to print the whole expression.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Tree");
+        debug.field("id", &self.id());
+        match expr::op(self) {
+            Some(op) => debug.field("op", &op),
+            None => debug.field("op", &format_args!("Unknown")),
+        };
+        debug.finish()
+    }
+}
+
+impl PartialEq for Tree {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for Tree {}
+
+impl Hash for Tree {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+// A `libfive_tree` is a handle to an immutable, reference-counted node
+// graph. The reference count is atomic (it is a `std::shared_ptr`) and
+// nothing in `libfive` ties a tree to the thread that created it.
+unsafe impl Send for Tree {}
+// All `libfive` calls taking a `&Tree` only read the graph. Evaluation and
+// meshing set up their own evaluators.
+unsafe impl Sync for Tree {}
+
+op_binary!(add, Add);
+op_binary!(div, Div);
+op_binary!(mul, Mul);
+op_binary!(rem, Rem);
+op_binary!(sub, Sub);
+
+impl Neg for Tree {
+    type Output = Tree;
+
+    fn neg(self) -> Self::Output {
+        Self(unsafe { sys::libfive_tree_unary(Op::Neg as _, self.0) })
+    }
+}
+
+/// Computes a mesh of `region` for each tree and saves it to the
+/// accompanying path in [`STL`](https://en.wikipedia.org/wiki/STL_(file_format))
+/// format.
+///
+/// The trees are meshed in parallel on [`rayon`]'s thread pool.
+///
+/// Returns one result per item, in the order of `items`. A failing item does
+/// not stop the others from being exported.
+#[cfg(feature = "rayon")]
+pub fn export_all_stl(
+    items: &[(Tree, PathBuf)],
+    region: &Region3,
+    resolution: f32,
+) -> Vec<Result<()>> {
+    use rayon::prelude::*;
+
+    items
+        .par_iter()
+        .map(|(tree, path)| {
+            let tree = tree.clone();
+            tree.write_stl(path, region, resolution)
+        })
+        .collect()
+}
+
+/// Maps `items` with `f` in parallel, preserving their order.
+///
+/// Uses [`rayon`]'s thread pool if the `rayon` feature is enabled and one
+/// scoped thread per available CPU otherwise.
+fn par_map<T: Sync, R: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(f).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |threads| threads.get());
+        let chunk_size = items.len().div_ceil(threads).max(1);
+
+        std::thread::scope(|scope| {
+            items
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(|| chunk.iter().map(&f).collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+}
+
+fn c_string_from_path<P: AsRef<Path>>(path: P) -> Result<CString> {
+    CString::new(path.as_ref().as_os_str().as_encoded_bytes())
+        .map_err(|_| Error::InvalidPath)
+}
+
+/// Checks that a grid of `resolution` samples per axis is not empty and that
+/// the positions of its samples fit in memory.
+fn validate_sample_count(resolution: &[u32]) -> Result<()> {
+    let max_count = isize::MAX as usize / std::mem::size_of::<[f32; 3]>();
+
+    match resolution
+        .iter()
+        .try_fold(1usize, |count, &n| count.checked_mul(n as usize))
+    {
+        Some(count) if 0 < count && count <= max_count => Ok(()),
+        _ => Err(Error::InvalidResolution),
+    }
+}
+
+fn validate_resolution(resolution: f32) -> Result<()> {
+    if resolution.is_finite() && resolution > 0.0 {
+        Ok(())
+    } else {
+        Err(Error::InvalidResolution)
+    }
+}
+
+fn validate_intervals(intervals: &[sys::libfive_interval]) -> Result<()> {
+    if intervals.iter().all(|interval| {
+        interval.lower.is_finite()
+            && interval.upper.is_finite()
+            && interval.lower < interval.upper
+    }) {
+        Ok(())
+    } else {
+        Err(Error::InvalidRegion)
+    }
+}
+
+/// Start of files written by [`Tree::save_versioned()`].
+const FREP_MAGIC: &[u8; 8] = b"LIBFIVE\0";
+/// Version of the header [`Tree::save_versioned()`] writes after
+/// [`FREP_MAGIC`].
+const FREP_VERSION: u32 = 2;
+
+/// Returns the version and git revision of `libfive`, separated by a space
+/// and cut to 255 bytes, as recorded by [`Tree::save_versioned()`].
+fn libfive_version() -> Vec<u8> {
+    let [version, revision] = unsafe {
+        [sys::libfive_git_version(), sys::libfive_git_revision()]
+            .map(|text| CStr::from_ptr(text).to_bytes())
+    };
+
+    let mut libfive_version = [version, revision].join(&b' ');
+    libfive_version.truncate(u8::MAX as usize);
+
+    libfive_version
+}
+
+/// Loads a tree from a file as written by `libfive`.
+fn load_frep(path: &CString) -> Result<Tree> {
+    match unsafe { sys::libfive_tree_load(path.as_ptr()).as_mut() } {
+        Some(tree) => Ok(Tree(tree as _)),
+        None => Err(Error::FileReadFailed),
+    }
+}
+
+#[test]
 fn test_2d() -> Result<()> {
     let circle = Tree::x().square() + Tree::y().square() - 1.0.into();
 
-    circle.write_svg(
-        "circle.svg",
-        &Region2::new(-2.0, 2.0, -2.0, 2.0),
-        0.0,
-        10.0,
+    circle.write_svg(
+        "circle.svg",
+        &Region2::new(-2.0, 2.0, -2.0, 2.0),
+        0.0,
+        10.0,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_3d() -> Result<()> {
+    let f_rep_shape =
+        Tree::sphere(1.0, TreeVec3::default()).difference_multi(vec![
+            Tree::sphere(0.6, TreeVec3::default()),
+            Tree::cylinder_z(0.6, 2.0, TreeVec3::new(0.0, 0.0, -1.0)),
+            Tree::cylinder_z(0.6, 2.0, TreeVec3::new(0.0, 0.0, -1.0))
+                .reflect_xz(),
+            Tree::cylinder_z(0.6, 2.0, TreeVec3::new(0.0, 0.0, -1.0))
+                .reflect_yz(),
+        ]);
+
+    f_rep_shape.write_stl(
+        "f-rep-shape.stl",
+        &Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0),
+        // Subdivisions
+        10.0,
+    )?;
+
+    Ok(())
+}
+
+/*
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_eval_3d() -> Result<()> {
+    //let mut variables = Variables::new();
+
+    //let inner_radius = variables.add("inner_radius", 0.6)?;
+
+    let csg_shape = Tree::sphere(1.0, TreeVec3::default())
+        .difference_multi(vec![
+            Tree::sphere(0.6, TreeVec3::default()),
+            Tree::cylinder_z(
+                0.6,
+                2.0,
+                TreeVec3::new(0.0, 0.0, -1.0),
+            ),
+            Tree::cylinder_z(
+                0.6,
+                2.0,
+                TreeVec3::new(0.0, 0.0, -1.0),
+            )
+            .reflect_xz(),
+            Tree::cylinder_z(
+                0.6,
+                2.0,
+                TreeVec3::new(0.0, 0.0, -1.0),
+            )
+            .reflect_yz(),
+        ]);
+
+    //let mut evaluator = Evaluator::new(&csg_shape, &variables);
+    //evaluator.update(&variables);
+
+    csg_shape.write_stl(
+        "csg_shape.stl",
+        &Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0),
+        10.0,
+    )?;
+    /*
+    variables.set("inner_radius", 0.4);
+    evaluator.update(&variables);
+
+    csg_shape.write_stl(
+        "csg_shape_0_4.stl",
+        &Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0),
+        10.0,
+    )?;*/
+
+    Ok(())
+}*/
+
+#[test]
+fn test_variables_inspection() -> Result<()> {
+    let mut variables = Variables::new();
+    assert!(variables.is_empty());
+
+    variables.add("a", 1.0)?;
+    variables.add("b", 2.0)?;
+    variables.add("c", 3.0)?;
+    variables.set("b", 20.0)?;
+
+    assert_eq!(variables.len(), 3);
+    assert!(variables.contains("a"));
+    assert!(!variables.contains("d"));
+    assert_eq!(variables.get("b"), Some(20.0));
+    assert_eq!(variables.get("d"), None);
+
+    let mut pairs = variables.iter().collect::<Vec<_>>();
+    pairs.sort_by(|a, b| a.0.cmp(b.0));
+    assert_eq!(pairs, vec![("a", 1.0), ("b", 20.0), ("c", 3.0)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_variables_debug_clone() -> Result<()> {
+    let mut variables = Variables::new();
+    variables.add("radius", 1.5)?;
+    variables.add("height", 2.0)?;
+    variables.add("angle", 0.25)?;
+    variables.remove("height")?;
+
+    assert_eq!(
+        format!("{:?}", variables),
+        r#"{"radius": 1.5, "angle": 0.25}"#
+    );
+
+    let mut clone = variables.clone();
+    clone.set("radius", 3.0)?;
+    assert_eq!(variables.get("radius"), Some(1.5));
+    assert_eq!(clone.get("radius"), Some(3.0));
+    assert_eq!(clone.variables, variables.variables);
+
+    Ok(())
+}
+
+#[test]
+fn test_variables_remove() -> Result<()> {
+    let mut variables = Variables::new();
+    variables.add("a", 1.0)?;
+    variables.add("b", 2.0)?;
+    variables.add("c", 3.0)?;
+
+    variables.remove("b")?;
+    assert_eq!(variables.remove("b"), Err(Error::VariableNotFound));
+
+    assert_eq!(variables.len(), 2);
+    assert!(!variables.contains("b"));
+    assert_eq!(variables.get("c"), Some(3.0));
+    variables.set("c", 30.0)?;
+
+    let mut pairs = variables.iter().collect::<Vec<_>>();
+    pairs.sort_by(|a, b| a.0.cmp(b.0));
+    assert_eq!(pairs, vec![("a", 1.0), ("c", 30.0)]);
+    assert_eq!(variables.sys_variables().size, 2);
+    assert_eq!(unsafe { *variables.sys_variables().values.add(1) }, 30.0);
+
+    variables.add("b", 4.0)?;
+    assert_eq!(variables.get("b"), Some(4.0));
+
+    variables.clear();
+    assert!(variables.is_empty());
+    assert_eq!(variables.iter().count(), 0);
+    assert_eq!(variables.sys_variables().size, 0);
+
+    Ok(())
+}
+
+// Best run under a sanitizer, e.g. with
+// `RUSTFLAGS=-Zsanitizer=address cargo +nightly test`, to catch reads of
+// buffers freed by a reallocation.
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_variables_reallocation() -> Result<()> {
+    let mut variables = Variables::new();
+    let radius = variables.add("radius", 1.0)?;
+
+    let sphere = Tree::sphere(radius, TreeVec3::default());
+    let mut evaluator = Evaluator::new(&sphere, &variables);
+
+    let capacity = variables.values.capacity();
+    for index in 0..1000 {
+        variables.add(&format!("unused_{}", index), index as f32)?;
+    }
+    assert_ne!(variables.values.capacity(), capacity);
+
+    variables.set("radius", 1.5)?;
+    evaluator.update(&variables)?;
+    let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+    let mesh = evaluator.to_triangle_mesh::<[f32; 3]>(&region)?;
+
+    assert!(mesh
+        .positions
+        .iter()
+        .all(|p| (p[0].hypot(p[1]).hypot(p[2]) - 1.5).abs() < 0.1));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_evaluator_update() -> Result<()> {
+    let mut variables = Variables::new();
+    let radius = variables.add("radius", 1.0)?;
+
+    let sphere = Tree::sphere(radius, TreeVec3::default());
+    let mut evaluator = Evaluator::new(&sphere, &variables);
+    assert!(!evaluator.update(&variables)?);
+
+    variables.set("radius", 1.5)?;
+    assert!(evaluator.update(&variables)?);
+    assert!(!evaluator.update(&variables)?);
+
+    let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+    let mesh = evaluator.to_triangle_mesh::<[f32; 3]>(&region)?;
+    let max_radius = mesh
+        .positions
+        .iter()
+        .map(|p| p[0].hypot(p[1]).hypot(p[2]))
+        .fold(0.0, f32::max);
+    assert!((max_radius - 1.5).abs() < 0.1);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_evaluator_to_triangle_mesh() -> Result<()> {
+    let mut variables = Variables::new();
+    let radius = variables.add("radius", 1.0)?;
+
+    let sphere = Tree::sphere(radius, TreeVec3::default());
+    let mut evaluator = Evaluator::new(&sphere, &variables);
+
+    let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+    let small = evaluator.to_triangle_mesh::<[f32; 3]>(&region)?;
+
+    variables.set("radius", 1.5)?;
+    evaluator.update(&variables)?;
+    let large = evaluator.to_triangle_mesh::<[f32; 3]>(&region)?;
+
+    assert!(!small.triangles.is_empty());
+    assert_ne!(small.positions, large.positions);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_evaluator_to_contour_2d() -> Result<()> {
+    let mut variables = Variables::new();
+    let radius = variables.add("radius", 0.5)?;
+
+    let cylinder = Tree::cylinder_z(radius, 2.0, TreeVec3::new(0.0, 0.0, -1.0));
+    let mut evaluator = Evaluator::new(&cylinder, &variables);
+
+    let region = Region2::new(-2.0, 2.0, -2.0, 2.0);
+    let max_extent = |evaluator: &Evaluator| {
+        evaluator
+            .to_contour_2d::<[f32; 2]>(region, 0.0, 10.0)
+            .unwrap()
+            .iter()
+            .flatten()
+            .fold(0.0f32, |extent, point| extent.max(point[0].abs()))
+    };
+
+    let small = max_extent(&evaluator);
+
+    variables.set("radius", 1.0)?;
+    evaluator.update(&variables)?;
+    let large = max_extent(&evaluator);
+
+    assert!((small - 0.5).abs() < 0.1);
+    assert!((large - 1.0).abs() < 0.1);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_bounds() {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+
+    let bounds = sphere
+        .bounds(&Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0))
+        .unwrap();
+
+    for interval in [bounds.0.X, bounds.0.Y, bounds.0.Z] {
+        assert!((interval.lower + 1.0).abs() < 0.1);
+        assert!((interval.upper - 1.0).abs() < 0.1);
+    }
+
+    assert!(sphere
+        .bounds(&Region3::new(2.0, 3.0, 2.0, 3.0, 2.0, 3.0))
+        .is_none());
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_write_stl_auto() -> Result<()> {
+    let path = std::env::temp_dir().join("libfive-sphere-auto.stl");
+
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let search = Region3::cube([0.0; 3], 4.0);
+    sphere.write_stl_auto(&path, &search, 10.0, 0.1)?;
+
+    let mesh = stl::read_binary_stl::<[f32; 3]>(
+        std::fs::File::open(&path).map_err(|_| Error::FileReadFailed)?,
+    )
+    .map_err(|_| Error::FileReadFailed)?;
+
+    assert!(!mesh.triangles.is_empty());
+    // The sphere must not be clipped by the region.
+    for axis in 0..3 {
+        let (min, max) = mesh.positions.iter().fold(
+            (f32::MAX, f32::MIN),
+            |(min, max), position| {
+                (min.min(position[axis]), max.max(position[axis]))
+            },
+        );
+        assert!((min + 1.0).abs() < 0.1 && (max - 1.0).abs() < 0.1);
+    }
+
+    // The region is the sphere's bounds, `±1`, plus the margin. It never
+    // clips them and is looser by at most a cell of the second pass,
+    // `2.25 / 64`.
+    let region = sphere.auto_region(&search, 10.0, 0.1)?;
+    for (min, max) in region.min().into_iter().zip(region.max()) {
+        assert!(-1.15 < min && min <= -1.1 && 1.1 <= max && max < 1.15);
+    }
+
+    // Bounds of a box off the origin.
+    let cuboid = Tree::box_exact(
+        TreeVec3::new(1.0, 2.0, 3.0),
+        TreeVec3::new(2.0, 4.0, 3.5),
+    );
+    let region = cuboid.auto_region(&search, 10.0, 0.0)?;
+    for ((min, max), (expected_min, expected_max)) in region
+        .min()
+        .into_iter()
+        .zip(region.max())
+        .zip([(1.0, 2.0), (2.0, 4.0), (3.0, 3.5)])
+    {
+        assert!(expected_min - 0.1 < min && min <= expected_min);
+        assert!(expected_max <= max && max < expected_max + 0.1);
+    }
+
+    assert_eq!(
+        Tree::half_space(TreeVec3::new(0.0, 0.0, 1.0), TreeVec3::default())
+            .write_stl_auto(&path, &search, 10.0, 0.1),
+        Err(Error::UnboundedShape)
+    );
+    assert_eq!(
+        sphere.write_stl_auto(&path, &Region3::cube([0.0; 3], 0.5), 10.0, 0.1),
+        Err(Error::UnboundedShape)
+    );
+    assert_eq!(
+        sphere.write_stl_auto(&path, &Region3::cube([3.0; 3], 1.0), 10.0, 0.1),
+        Err(Error::EmptyResult)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_region_center_size() {
+    let region = Region2::new(-1.0, 3.0, 2.0, 4.0);
+    assert_eq!(region.center(), [1.0, 3.0]);
+    assert_eq!(region.size(), [4.0, 2.0]);
+    assert_eq!(
+        Region2::from_center_size(region.center(), region.size()),
+        region
+    );
+    assert_eq!(
+        Region2::square([0.0; 2], 2.0),
+        Region2::new(-2.0, 2.0, -2.0, 2.0)
+    );
+
+    let region = Region3::new(-1.0, 3.0, 2.0, 4.0, -6.0, -2.0);
+    assert_eq!(region.min(), [-1.0, 2.0, -6.0]);
+    assert_eq!(region.max(), [3.0, 4.0, -2.0]);
+    assert_eq!(region.center(), [1.0, 3.0, -4.0]);
+    assert_eq!(region.size(), [4.0, 2.0, 4.0]);
+    assert_eq!(
+        Region3::from_center_size(region.center(), region.size()),
+        region
+    );
+    assert_eq!(
+        Region3::cube([1.0; 3], 1.0),
+        Region3::new(0.0, 2.0, 0.0, 2.0, 0.0, 2.0)
+    );
+}
+
+#[test]
+fn test_invalid_resolution() {
+    let circle = Tree::x().square() + Tree::y().square() - 1.0.into();
+    let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+
+    for resolution in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+        assert_eq!(
+            circle.write_stl("invalid.stl", &region, resolution),
+            Err(Error::InvalidResolution)
+        );
+        assert!(circle
+            .to_triangle_mesh::<[f32; 3]>(&region, resolution)
+            .is_none());
+    }
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_stl_bytes() -> Result<()> {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+
+    let path = std::env::temp_dir().join("libfive-sphere.stl");
+    sphere.write_stl(&path, &region, 10.0)?;
+    let file = std::fs::read(&path).map_err(|_| Error::FileReadFailed)?;
+
+    let bytes = sphere.to_stl_bytes(&region, 10.0)?;
+    assert_eq!(bytes.len(), file.len());
+    // Triangle count.
+    assert_eq!(bytes[80..84], file[80..84]);
+
+    let from_bytes = stl::read_binary_stl::<[f32; 3]>(bytes.as_slice())
+        .map_err(|_| Error::FileReadFailed)?;
+    let from_file = stl::read_binary_stl::<[f32; 3]>(file.as_slice())
+        .map_err(|_| Error::FileReadFailed)?;
+    assert_eq!(from_bytes.positions, from_file.positions);
+    assert_eq!(from_bytes.triangles, from_file.triangles);
+
+    let mut written = Vec::new();
+    sphere
+        .write_stl_to(&mut written, &region, 10.0)
+        .map_err(|_| Error::FileWriteFailed)?;
+    assert_eq!(written, bytes);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_stl_formats() -> Result<()> {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+
+    let binary_path = std::env::temp_dir().join("libfive-binary.stl");
+    let ascii_path = std::env::temp_dir().join("libfive-ascii.stl");
+    sphere.write_stl_with_format(
+        &binary_path,
+        &region,
+        10.0,
+        StlFormat::Binary,
+    )?;
+    sphere.write_stl_with_format(
+        &ascii_path,
+        &region,
+        10.0,
+        StlFormat::Ascii,
+    )?;
+
+    let binary =
+        std::fs::read(&binary_path).map_err(|_| Error::FileReadFailed)?;
+    let ascii = std::fs::read_to_string(&ascii_path)
+        .map_err(|_| Error::FileReadFailed)?;
+
+    assert!(!binary.starts_with(b"solid"));
+    let count = u32::from_le_bytes(binary[80..84].try_into().unwrap());
+    assert_eq!(binary.len(), 84 + 50 * count as usize);
+
+    assert!(ascii.starts_with("solid "));
+    assert!(ascii.trim_end().ends_with("endsolid libfive"));
+    assert_eq!(ascii.matches("facet normal").count(), count as usize);
+
+    Ok(())
+}
+
+#[test]
+fn test_paths() -> Result<()> {
+    let circle = Tree::x().square() + Tree::y().square() - 1.0.into();
+    let region = Region2::new(-2.0, 2.0, -2.0, 2.0);
+
+    circle.write_svg(
+        std::env::temp_dir().join("libfive-circle.svg"),
+        &region,
+        0.0,
+        10.0,
+    )?;
+
+    assert_eq!(
+        circle.write_svg("circle\0.svg", &region, 0.0, 10.0),
+        Err(Error::InvalidPath)
+    );
+    assert_eq!(
+        circle.write_svg(
+            std::env::temp_dir()
+                .join("libfive-missing")
+                .join("circle.svg"),
+            &region,
+            0.0,
+            10.0
+        ),
+        Err(Error::FileWriteFailed)
+    );
+    assert_eq!(circle.save("circle\0.frep"), Err(Error::InvalidPath));
+
+    Ok(())
+}
+
+#[test]
+fn test_bitmap_save_pbm() -> Result<()> {
+    let circle = Tree::x().square() + Tree::y().square() - 1.0.into();
+    let bitmap =
+        circle.to_bitmap(&Region2::new(-2.0, 2.0, -2.0, 2.0), 0.0, 10.0);
+    let (width, height) = (bitmap.width(), bitmap.height());
+    assert!(bitmap.pixel(width / 2, height / 2));
+
+    let path = std::env::temp_dir().join("libfive-circle.pbm");
+    bitmap.save_pbm(&path)?;
+
+    let pbm = std::fs::read(&path).map_err(|_| Error::FileReadFailed)?;
+    let header = format!("P4\n{} {}\n", width, height);
+    assert!(pbm.starts_with(header.as_bytes()));
+
+    let stride = width.div_ceil(8) as usize;
+    assert_eq!(pbm.len(), header.len() + stride * height as usize);
+
+    let center = header.len()
+        + (height - 1 - height / 2) as usize * stride
+        + (width / 2) as usize / 8;
+    assert_ne!(pbm[center] & (0x80 >> ((width / 2) % 8)), 0);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn test_bitmap_to_image() {
+    // Occupies the upper half of the region.
+    let upper_half = -Tree::y();
+    let bitmap =
+        upper_half.to_bitmap(&Region2::new(-2.0, 2.0, -2.0, 2.0), 0.0, 10.0);
+    let image = bitmap.to_image();
+
+    assert_eq!(image.dimensions(), (bitmap.width(), bitmap.height()));
+    assert_eq!(image.get_pixel(0, 0).0, [255]);
+    assert_eq!(image.get_pixel(0, image.height() - 1).0, [0]);
+}
+
+#[test]
+fn test_bitmap_iter_pixels() {
+    let circle = Tree::x().square() + Tree::y().square() - 1.0.into();
+    let mut bitmap =
+        circle.to_bitmap(&Region2::new(-2.0, 3.0, -2.0, 2.0), 0.0, 2.0);
+    let width = bitmap.width();
+
+    let mut flat = vec![false; bitmap.as_slice().len()];
+    for (x, y, value) in bitmap.iter_pixels() {
+        assert_eq!(value, bitmap.pixel(x, y));
+        flat[(y * width + x) as usize] = value;
+    }
+    assert_eq!(flat, bitmap.as_slice());
+
+    *bitmap.pixel_mut(width - 1, 0) = true;
+    assert!(bitmap.as_slice()[width as usize - 1]);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_voxel_grid() {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let grid = sphere
+        .to_voxel_grid(&Region3::cube([0.0; 3], 1.5), [30, 30, 30])
+        .unwrap();
+
+    assert_eq!(grid.dimensions(), [30, 30, 30]);
+    assert!(grid.at(15, 15, 15));
+    assert!(!grid.at(0, 0, 0));
+
+    let voxel_volume = (3.0f32 / 30.0).powi(3);
+    let volume = grid.as_slice().iter().filter(|&&voxel| voxel).count() as f32
+        * voxel_volume;
+    let sphere_volume = 4.0 / 3.0 * core::f32::consts::PI;
+    assert!((volume - sphere_volume).abs() < 0.05 * sphere_volume);
+
+    assert_eq!(
+        sphere.to_voxel_grid(&Region3::cube([0.0; 3], 1.5), [30, 0, 30]),
+        Err(Error::InvalidResolution)
+    );
+    assert_eq!(
+        sphere.to_voxel_grid(&Region3::cube([0.0; 3], 0.0), [30, 30, 30]),
+        Err(Error::InvalidRegion)
+    );
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_sdf_grid() {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    // Voxel centers along x are at -3.5, -2.5, ..., 3.5.
+    let grid = sphere
+        .to_sdf_grid(&Region3::new(-4.0, 4.0, -0.5, 0.5, -0.5, 0.5), [8, 1, 1])
+        .unwrap();
+
+    assert_eq!(grid.len(), 8);
+    // Inside at 0.5, outside at 1.5.
+    assert!(grid[4] < 0.0 && 0.0 < grid[5]);
+    // Grows linearly with the distance to the surface.
+    for x in 5..8 {
+        assert!((grid[x] - (x as f32 - 3.5 - 1.0)).abs() < 1.0e-4);
+        assert!((grid[7 - x] - grid[x]).abs() < 1.0e-4);
+    }
+
+    assert_eq!(
+        sphere.to_sdf_grid(
+            &Region3::new(4.0, -4.0, -0.5, 0.5, -0.5, 0.5),
+            [8, 1, 1]
+        ),
+        Err(Error::InvalidRegion)
+    );
+    assert_eq!(
+        sphere.to_sdf_grid(
+            &Region3::cube([0.0; 3], 1.0),
+            [u32::MAX, u32::MAX, u32::MAX]
+        ),
+        Err(Error::InvalidResolution)
+    );
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_heightmap() {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let heightmap = sphere
+        .to_heightmap(&Region3::cube([0.0; 3], 2.0), [9, 9])
+        .unwrap();
+
+    assert_eq!(heightmap.len(), 81);
+    // Pixel centers are on the axes for odd resolutions.
+    assert!((heightmap[4 * 9 + 4].unwrap() - 1.0).abs() < 1.0e-3);
+    assert_eq!(heightmap[0], None);
+    // Off center the height follows the sphere.
+    let x = 4.0f32 / 9.0;
+    let expected = (1.0 - x * x).sqrt();
+    assert!((heightmap[4 * 9 + 5].unwrap() - expected).abs() < 1.0e-3);
+
+    assert_eq!(
+        sphere.to_heightmap(&Region3::cube([0.0; 3], 2.0), [0, 9]),
+        Err(Error::InvalidResolution)
+    );
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_mesh_cancel() {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let region = Region3::cube([0.0; 3], 2.0);
+
+    let options = MeshOptions::new(10.0);
+    options.cancel.store(true, Ordering::Relaxed);
+    assert!(matches!(
+        sphere.to_triangle_mesh_with::<[f32; 3]>(&region, &options),
+        Err(Error::Cancelled)
+    ));
+
+    let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut options = MeshOptions::new(10.0);
+    options.progress = Some(Box::new({
+        let progress = progress.clone();
+        move |value| progress.lock().unwrap().push(value)
+    }));
+    assert!(sphere
+        .to_triangle_mesh_with::<[f32; 3]>(&region, &options)
+        .is_ok());
+    let progress = progress.lock().unwrap();
+    assert_eq!(progress.first(), Some(&0.0));
+    assert!(progress.iter().all(|value| (0.0..=1.0).contains(value)));
+
+    // Cancelling from the progress callback stops meshing.
+    let mut options = MeshOptions::new(10.0);
+    options.progress = Some(Box::new({
+        let cancel = options.cancel.clone();
+        move |_| cancel.store(true, Ordering::Relaxed)
+    }));
+    assert!(matches!(
+        sphere.to_triangle_mesh_with::<[f32; 3]>(&region, &options),
+        Err(Error::Cancelled)
+    ));
+
+    // So does a panic in it.
+    let mut options = MeshOptions::new(10.0);
+    options.progress = Some(Box::new(|_| panic!("progress")));
+    assert!(matches!(
+        sphere.to_triangle_mesh_with::<[f32; 3]>(&region, &options),
+        Err(Error::Cancelled)
+    ));
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_spawn_mesh() -> Result<()> {
+    let region = Region3::cube([0.0; 3], 2.0);
+
+    let handles = (1..=4)
+        .map(|i| {
+            Tree::sphere(0.25 * i as f32, TreeVec3::default())
+                .spawn_mesh::<[f32; 3]>(&region, 10.0)
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert!(!handle.join().unwrap()?.triangles.is_empty());
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_tree_threads() {
+    let shape = Tree::sphere(1.0, TreeVec3::default())
+        .difference(Tree::sphere(0.6, TreeVec3::default()));
+    let region = Region3::cube([0.0; 3], 2.0);
+
+    let triangles = shape
+        .to_triangle_mesh::<[f32; 3]>(&region, 5.0)
+        .unwrap()
+        .triangles
+        .len();
+
+    // Clones moved into threads.
+    let handles = (0..16)
+        .map(|_| {
+            let shape = shape.clone();
+            std::thread::spawn(move || {
+                shape
+                    .to_triangle_mesh::<[f32; 3]>(&region, 5.0)
+                    .unwrap()
+                    .triangles
+                    .len()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // The same tree shared between threads.
+    std::thread::scope(|scope| {
+        for _ in 0..16 {
+            scope.spawn(|| {
+                assert!(shape.eval([0.0, 0.0, 0.8]) < 0.0);
+                let _ = shape.clone();
+            });
+        }
+    });
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), triangles);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "rayon", feature = "stdlib"))]
+fn test_export_all_stl() {
+    let items = (1..=4)
+        .map(|i| {
+            (
+                Tree::sphere(0.25 * i as f32, TreeVec3::default()),
+                std::env::temp_dir().join(format!("libfive-sphere-{}.stl", i)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let results = export_all_stl(&items, &Region3::cube([0.0; 3], 2.0), 10.0);
+
+    assert_eq!(results.len(), items.len());
+    for (result, (_, path)) in results.into_iter().zip(&items) {
+        assert_eq!(result, Ok(()));
+        assert!(path.exists());
+    }
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_slice_stack() {
+    let cone = Tree::cone_z(1.0, 1.0, TreeVec3::default());
+    let z_values = [0.1, 0.3, 0.5, 0.7, 0.9];
+
+    let stack = cone.to_slice_stack(
+        &Region2::new(-1.5, 1.5, -1.5, 1.5),
+        &z_values,
+        20.0,
+    );
+    assert_eq!(stack.len(), z_values.len());
+
+    let areas = stack
+        .iter()
+        .map(|bitmap| bitmap.as_slice().iter().filter(|&&p| p).count())
+        .collect::<Vec<_>>();
+    assert!(areas.windows(2).all(|pair| pair[1] < pair[0]));
+}
+
+#[test]
+fn test_tree_id() {
+    use std::collections::hash_map::DefaultHasher;
+
+    let hash = |tree: &Tree| {
+        let mut hasher = DefaultHasher::new();
+        tree.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let tree = Tree::x().square() + Tree::y().square();
+    let clone = tree.clone();
+
+    assert_eq!(tree.id(), clone.id());
+    assert!(tree == clone);
+    assert_eq!(hash(&tree), hash(&clone));
+
+    assert!(tree != Tree::x().square() + Tree::y().square());
+}
+
+#[test]
+fn test_optimized() {
+    let sum = Tree::from(2.0) + Tree::from(3.0);
+    assert_eq!(sum.optimized().as_f32(), Ok(5.0));
+
+    let scaled = (Tree::x() * (Tree::from(2.0) * Tree::from(4.0))).optimized();
+    assert!(scaled.as_f32().is_err());
+    assert_eq!(scaled.eval([1.5, 0.0, 0.0]), 12.0);
+
+    // Folding must not go through printed, rounded constants.
+    let third = (Tree::from(1.0) / Tree::from(3.0)).optimized();
+    assert_eq!(third.as_f32(), Ok(1.0 / 3.0));
+
+    let remainder = Tree::from(-1.0).rem(Tree::from(3.0)).optimized();
+    assert_eq!(remainder.as_f32(), Ok(2.0));
+
+    let mut variables = Variables::new();
+    let a = variables.add("a", 1.0).unwrap();
+    let tree = (a * (Tree::from(2.0) + Tree::from(1.0))).optimized();
+    assert_eq!(tree.node_count(), Some(3));
+    assert!(tree.as_f32().is_err());
+}
+
+#[test]
+fn test_to_sexpr() {
+    let tree = Tree::x().square() + Tree::y().square();
+    let sexpr = tree.to_sexpr();
+
+    assert_eq!(sexpr.matches("square").count(), 2);
+    assert_eq!(sexpr.matches('+').count(), 1);
+    assert_eq!(tree.to_string(), sexpr);
+}
+
+#[test]
+fn test_node_count_depth() {
+    let x = Tree::x();
+    let tree = x.clone() * x;
+    assert_eq!(tree.node_count(), Some(2));
+    assert_eq!(tree.depth(), Some(2));
+
+    let tree = (Tree::x().square() + Tree::y()).sqrt();
+    assert_eq!(tree.node_count(), Some(5));
+    assert_eq!(tree.depth(), Some(4));
+
+    let mut variables = Variables::new();
+    let a = variables.add("a", 1.0).unwrap();
+    assert_eq!((Tree::x() + a.clone()).node_count(), Some(3));
+    assert_eq!((Tree::x() + a).depth(), Some(2));
+
+    // Printed in full this would be 2⁴⁰ leaves.
+    let mut tree = Tree::x();
+    for _ in 0..40 {
+        tree = tree.clone() + tree;
+    }
+    assert_eq!(tree.node_count(), Some(41));
+    assert_eq!(tree.depth(), Some(41));
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_tree_default() {
+    let region = Region3::cube([0.0; 3], 1.5);
+    let sphere = || Tree::sphere(1.0, TreeVec3::default());
+
+    let union = Tree::default()
+        .union(sphere())
+        .to_triangle_mesh::<[f32; 3]>(&region, 10.0)
+        .unwrap();
+    let mesh = sphere()
+        .to_triangle_mesh::<[f32; 3]>(&region, 10.0)
+        .unwrap();
+
+    assert!(!mesh.triangles.is_empty());
+    assert_eq!(union.positions, mesh.positions);
+    assert_eq!(union.triangles, mesh.triangles);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_union_intersection_all() {
+    let circles =
+        || (0..10).map(|i| Tree::circle(0.4, TreeVec2::new(i as f32, 0.0)));
+
+    let union = Tree::union_all(circles());
+    for i in 0..10 {
+        assert!(union.eval([i as f32, 0.0, 0.0]) < 0.0);
+    }
+    assert!(union.eval([0.5, 0.0, 0.0]) > 0.0);
+
+    assert!(Tree::intersection_all(circles()).eval([0.0; 3]) > 0.0);
+
+    assert!(Tree::union_all(Vec::new()).eval([0.0; 3]) > 0.0);
+    assert!(Tree::intersection_all(Vec::new()).eval([0.0; 3]) < 0.0);
+
+    assert!(
+        Tree::emptiness()
+            .difference_multi(circles().skip(1))
+            .union_multi(circles().take(1))
+            .eval([0.0; 3])
+            < 0.0
+    );
+
+    // Without `trees` the `*_multi()` operations return `self`.
+    let circle = Tree::circle(0.4, TreeVec2::default());
+    assert_eq!(circle.clone().union_multi(Vec::new()), circle);
+    assert_eq!(circle.clone().intersection_multi(Vec::new()), circle);
+    assert_eq!(circle.clone().difference_multi(Vec::new()), circle);
+}
+
+#[test]
+fn test_min_max_all_clamp() {
+    let values = || [3.0, -1.0, 2.0].into_iter().map(Tree::from);
+    assert_eq!(Tree::min_all(values()).eval([0.0; 3]), -1.0);
+    assert_eq!(Tree::max_all(values()).eval([0.0; 3]), 3.0);
+    assert_eq!(Tree::min_all(Vec::new()).eval([0.0; 3]), f32::INFINITY);
+    assert_eq!(Tree::max_all(Vec::new()).eval([0.0; 3]), f32::NEG_INFINITY);
+
+    let clamped = Tree::x().clamp(0.0, 1.0);
+    assert_eq!(clamped.eval([-2.0, 0.0, 0.0]), 0.0);
+    assert_eq!(clamped.eval([0.25, 0.0, 0.0]), 0.25);
+    assert_eq!(clamped.eval([3.0, 0.0, 0.0]), 1.0);
+}
+
+#[test]
+fn test_mix() {
+    let a = || Tree::x() + Tree::y();
+    let b = || Tree::z().square();
+    let points = [[0.0, 0.0, 0.0], [1.0, -2.0, 3.0], [-0.5, 4.0, -1.5]];
+
+    let start = Tree::mix(a(), b(), 0.0);
+    let end = Tree::mix(a(), b(), 1.0);
+    let middle = Tree::mix(a(), b(), 0.5);
+
+    for point in points {
+        assert_eq!(start.eval(point), a().eval(point));
+        assert_eq!(end.eval(point), b().eval(point));
+        assert_eq!(
+            middle.eval(point),
+            0.5 * (a().eval(point) + b().eval(point))
+        );
+    }
+}
+
+#[test]
+fn test_step_smoothstep() {
+    let step = Tree::step(1.0, Tree::x());
+    assert_eq!(step.eval([0.5, 0.0, 0.0]), 0.0);
+    assert_eq!(step.eval([1.0, 0.0, 0.0]), 1.0);
+    assert_eq!(step.eval([1.5, 0.0, 0.0]), 1.0);
+
+    let smoothstep = Tree::smoothstep(1.0, 3.0, Tree::x());
+    assert_eq!(smoothstep.eval([0.0, 0.0, 0.0]), 0.0);
+    assert_eq!(smoothstep.eval([1.0, 0.0, 0.0]), 0.0);
+    assert_eq!(smoothstep.eval([1.5, 0.0, 0.0]), 0.15625);
+    assert_eq!(smoothstep.eval([2.0, 0.0, 0.0]), 0.5);
+    assert_eq!(smoothstep.eval([2.5, 0.0, 0.0]), 0.84375);
+    assert_eq!(smoothstep.eval([3.0, 0.0, 0.0]), 1.0);
+    assert_eq!(smoothstep.eval([4.0, 0.0, 0.0]), 1.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_length_distance() {
+    let length = Tree::length3(&TreeVec3::new(3.0, 4.0, 0.0)).optimized();
+    assert_eq!(length.as_f32(), Ok(5.0));
+
+    let length = Tree::length2(&TreeVec2::new(-6.0, 8.0)).optimized();
+    assert_eq!(length.as_f32(), Ok(10.0));
+
+    let distance = Tree::distance3(
+        &TreeVec3::new(1.0, 2.0, 3.0),
+        &TreeVec3 {
+            x: Tree::x(),
+            y: Tree::y(),
+            z: Tree::z(),
+        },
+    );
+    assert_eq!(distance.eval([3.0, 5.0, 9.0]), 7.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_vec3_algebra() {
+    let a = TreeVec3::new(1.0, 2.0, 3.0);
+    let b = TreeVec3::new(4.0, -5.0, 6.0);
+    let components =
+        |v: TreeVec3| [v.x, v.y, v.z].map(|c| c.optimized().as_f32().unwrap());
+
+    assert_eq!(a.dot(&b).optimized().as_f32(), Ok(12.0));
+    assert_eq!(components(a.cross(&b)), [27.0, 6.0, -13.0]);
+    assert_eq!(
+        components(TreeVec3::new(0.0, 3.0, 4.0).normalized()),
+        [0.0, 0.6, 0.8]
+    );
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_repeat() {
+    let sphere = || Tree::sphere(0.3, TreeVec3::default());
+    let period = || TreeVec3::new(2.0, 2.0, 2.0);
+
+    let repeated = sphere().repeat_xyz(period());
+    assert!(repeated.eval([0.0, 0.0, 0.0]) < 0.0);
+    assert!(repeated.eval([2.0, 0.0, 0.0]) < 0.0);
+    assert!(repeated.eval([-2.0, 2.0, 0.0]) < 0.0);
+    assert!(repeated.eval([1.0, 0.0, 0.0]) > 0.0);
+
+    let limited = sphere().repeat_limited(period(), [2, 1, 1]);
+    assert!(limited.eval([0.0, 0.0, 0.0]) < 0.0);
+    assert!(limited.eval([2.0, 0.0, 0.0]) < 0.0);
+    assert!(limited.eval([4.0, 0.0, 0.0]) > 0.0);
+    assert!(limited.eval([-2.0, 0.0, 0.0]) > 0.0);
+    assert!(limited.eval([0.0, 2.0, 0.0]) > 0.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_symmetric_polar_z() {
+    let shape = Tree::circle(0.3, TreeVec2::new(1.0, 0.2))
+        .symmetric_polar_z(6, TreeVec2::default());
+    let wedge = std::f32::consts::TAU / 6.0;
+
+    for (radius, angle) in [(0.5, 0.1), (1.0, 0.3), (1.5, 2.0), (0.8, -1.0)] {
+        let point =
+            |angle: f32| [radius * angle.cos(), radius * angle.sin(), 0.0];
+        let value = shape.eval(point(angle));
+        assert!((value - shape.eval(point(angle + wedge))).abs() < 1e-5);
+    }
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_revolve_x_z() {
+    let profile = || Tree::circle(0.3, TreeVec2::new(1.0, 0.0));
+
+    let around_z = profile().revolve_z(0.0);
+    for angle in [0.0f32, 0.7, 2.0, 4.5] {
+        let (sin, cos) = angle.sin_cos();
+        assert!(around_z.eval([cos, sin, 0.0]) < 0.0);
+        assert!(around_z.eval([0.5 * cos, 0.5 * sin, 0.0]) > 0.0);
+
+        let value = around_z.eval([1.2 * cos, 1.2 * sin, 0.1]);
+        assert!((value - around_z.eval([1.2, 0.0, 0.1])).abs() < 1e-5);
+    }
+
+    let profile = || Tree::circle(0.3, TreeVec2::new(0.0, 1.0));
+
+    let around_x = profile().revolve_x(0.0);
+    assert!(around_x.eval([0.0, 1.0, 0.0]) < 0.0);
+    assert!(around_x.eval([0.0, 0.0, 1.0]) < 0.0);
+    assert!(around_x.eval([0.0, 0.0, -1.0]) < 0.0);
+    assert!(around_x.eval([1.0, 0.0, 1.0]) > 0.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_sweep() {
+    let pipe = Tree::sweep(
+        Tree::circle(0.2, TreeVec2::default()),
+        &[
+            TreeVec3::new(0.0, 0.0, 0.0),
+            TreeVec3::new(2.0, 0.0, 0.0),
+            TreeVec3::new(2.0, 2.0, 0.0),
+        ],
+        0.2,
+    );
+
+    assert!(pipe.eval([1.0, 0.0, 0.0]) < 0.0);
+    assert!(pipe.eval([2.0, 1.0, 0.0]) < 0.0);
+    assert!(pipe.eval([2.0, 0.0, 0.0]) < 0.0);
+    assert!(pipe.eval([1.0, 0.1, 0.1]) < 0.0);
+
+    assert!(pipe.eval([1.0, 1.0, 0.0]) > 0.0);
+    assert!(pipe.eval([1.0, 0.0, 0.5]) > 0.0);
+    assert!(pipe.eval([-0.5, 0.0, 0.0]) > 0.0);
+    assert!(pipe.eval([2.0, 2.5, 0.0]) > 0.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_extrude_taper_z() {
+    let part = Tree::extrude_taper_z(
+        Tree::circle(1.0, TreeVec2::default()),
+        0.0,
+        1.0,
+        0.5,
+        1.0,
+    );
+
+    assert!(part.eval([0.8, 0.0, 0.05]) < 0.0);
+    assert!(part.eval([0.8, 0.0, 0.95]) > 0.0);
+    assert!(part.eval([0.0, 0.4, 0.95]) < 0.0);
+    assert!(part.eval([0.0, 0.0, 1.05]) > 0.0);
+
+    let region = Region2::new(-1.5, 1.5, -1.5, 1.5);
+    let area = |z| {
+        part.to_bitmap(&region, z, 20.0)
+            .as_slice()
+            .iter()
+            .filter(|&&pixel| pixel)
+            .count()
+    };
+    assert!(area(0.9) < area(0.1));
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_array_along() {
+    let spheres = Tree::array_along(
+        Tree::sphere(0.3, TreeVec3::default()),
+        5,
+        TreeVec3::new(1.0, 1.0, 0.0),
+    );
+
+    for i in 0..5 {
+        assert!(spheres.eval([i as f32, i as f32, 0.0]) < 0.0);
+        assert!(spheres.eval([i as f32 + 0.5, i as f32, 0.0]) > 0.0);
+    }
+    assert!(spheres.eval([5.0, 5.0, 0.0]) > 0.0);
+    assert!(spheres.eval([-1.0, -1.0, 0.0]) > 0.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_text_aligned() {
+    let x_extent = |tree: &Tree| {
+        let hint = Region3::new(-8.0, 8.0, -2.0, 4.0, -1.0, 1.0);
+        let bounds = tree.bounds(&hint).and_then(|coarse| tree.bounds(&coarse));
+        let bounds = bounds.unwrap();
+        (bounds.min()[0], bounds.max()[0])
+    };
+
+    let bounds = Tree::text_bounds("Hello").unwrap();
+    assert!(bounds.size()[0] > 1.0);
+    assert!(bounds.min()[1] > -0.5 && bounds.max()[1] < 1.5);
+
+    let (min, max) = x_extent(
+        &Tree::text_aligned(
+            "Hello",
+            TreeVec2::new(2.0, 1.0),
+            TextAlign::Center,
+        )
+        .unwrap(),
+    );
+    assert!((0.5 * (min + max) - 2.0).abs() < 0.1);
+
+    let (_, max) = x_extent(
+        &Tree::text_aligned("Hello", TreeVec2::new(2.0, 1.0), TextAlign::Right)
+            .unwrap(),
+    );
+    assert!((max - 2.0).abs() < 0.1);
+
+    assert_eq!(Tree::text_bounds("").unwrap().size(), [0.0, 0.0]);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_text_block() {
+    let y_extent = |tree: &Tree| {
+        let hint = Region3::new(-2.0, 4.0, -6.0, 3.0, -1.0, 1.0);
+        let bounds = tree.bounds(&hint).and_then(|coarse| tree.bounds(&coarse));
+        let bounds = bounds.unwrap();
+        (bounds.min()[1], bounds.max()[1])
+    };
+
+    let block = |txt| {
+        Tree::text_block(txt, TreeVec2::default(), 1.5, TextAlign::Left)
+            .unwrap()
+    };
+
+    let (first_min, first_max) = y_extent(&block("AB"));
+    let (second_min, second_max) = y_extent(&block("\nAB"));
+    let (third_min, _) = y_extent(&block("\n\nAB"));
+    assert!(second_max < first_min);
+    assert!((first_max - second_max - 1.5).abs() < 0.2);
+    assert!((second_min - third_min - 1.5).abs() < 0.2);
+
+    let (both_min, both_max) = y_extent(&block("AB\nAB"));
+    assert!((both_min - second_min).abs() < 0.2);
+    assert!((both_max - first_max).abs() < 0.2);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_text_nul() {
+    let label = "Hello\0World";
+
+    assert_eq!(
+        Tree::text(label, TreeVec2::default()).err(),
+        Some(Error::InvalidText)
+    );
+    assert_eq!(
+        Tree::text_aligned(label, TreeVec2::default(), TextAlign::Center).err(),
+        Some(Error::InvalidText)
+    );
+    assert_eq!(
+        Tree::text_block(label, TreeVec2::default(), 1.5, TextAlign::Left)
+            .err(),
+        Some(Error::InvalidText)
+    );
+    assert_eq!(Tree::text_bounds(label), Err(Error::InvalidText));
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_heightfield() {
+    let heights = [1.0, 2.0, 3.0, 4.0];
+    let terrain = Tree::heightfield(
+        &heights,
+        2,
+        2,
+        &Region2::new(0.0, 1.0, 0.0, 1.0),
+        0.5,
+    );
+
+    let corners = [[0.01, 0.01], [0.99, 0.01], [0.01, 0.99], [0.99, 0.99]];
+    for ([x, y], height) in corners.into_iter().zip(heights) {
+        let z = 0.5 * height;
+        assert!(terrain.eval([x, y, z - 0.1]) < 0.0);
+        assert!(terrain.eval([x, y, z + 0.1]) > 0.0);
+    }
+    assert!(terrain.eval([0.5, 0.5, 1.25]).abs() < 1e-5);
+
+    assert!(terrain.eval([0.5, 0.5, -0.1]) > 0.0);
+    assert!(terrain.eval([1.5, 0.5, 0.5]) > 0.0);
+}
+
+#[test]
+fn test_perlin3() {
+    let points = [[0.3, 1.7, -2.2], [5.5, -0.25, 0.8], [-3.1, 2.9, 4.4]];
+    let sample = |seed| {
+        let noise = Tree::perlin3(2.0, seed);
+        points.map(|point| noise.eval(point))
+    };
+
+    let values = sample(7);
+    assert_eq!(values, sample(7));
+    assert_ne!(values, sample(8));
+    assert!(values.iter().all(|value| value.abs() <= 1.5));
+
+    // Zero on the lattice.
+    assert_eq!(Tree::perlin3(2.0, 7).eval([1.5, -2.0, 3.0]), 0.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_project_to_surface() {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+
+    assert_eq!(sphere.gradient([2.0, 0.0, 0.0]), [1.0, 0.0, 0.0]);
+
+    for point in [
+        [2.0, 0.0, 0.0],
+        [0.1, -0.2, 0.3],
+        [-3.0, 4.0, 1.0],
+        [0.5, 0.5, -0.5],
+    ] {
+        let [x, y, z] = sphere.project_to_surface(point, 10);
+        assert!(((x * x + y * y + z * z).sqrt() - 1.0).abs() < 1e-4);
+    }
+
+    assert_eq!(sphere.project_to_surface([0.0; 3], 10), [0.0; 3]);
+}
+
+#[test]
+fn test_contour_length_area() {
+    let square = vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+    assert_eq!(contour_length(&square), 4.0);
+    assert_eq!(contour_signed_area(&square), 1.0);
+
+    let mut closed = square.clone();
+    closed.push(square[0]);
+    assert_eq!(contour_length(&closed), 4.0);
+    assert_eq!(contour_signed_area(&closed), 1.0);
+
+    let clockwise = square.into_iter().rev().collect::<Contour<[f32; 2]>>();
+    assert_eq!(contour_signed_area(&clockwise), -1.0);
+
+    assert_eq!(contour_length::<[f32; 2]>(&Vec::new()), 0.0);
+}
+
+#[test]
+fn test_classify_contours() {
+    let square = |min: f32, max: f32| {
+        vec![[min, min], [max, min], [max, max], [min, max]]
+    };
+    let contours = vec![
+        // Hole, listed before its outline.
+        square(-1.0, 1.0).into_iter().rev().collect(),
+        square(-2.0, 2.0),
+        // Island inside the hole.
+        square(-0.5, 0.5),
+        // Separate outline.
+        square(3.0, 4.0),
+    ];
+
+    let nodes = classify_contours::<[f32; 2]>(&contours);
+
+    assert_eq!(nodes[0].parent, Some(1));
+    assert_eq!(nodes[1].parent, None);
+    assert_eq!(nodes[1].children, vec![0]);
+    assert_eq!(nodes[2].parent, Some(0));
+    assert_eq!(nodes[0].children, vec![2]);
+    assert_eq!(nodes[3], ContourNode::default());
+
+    assert!(nodes[0].is_hole());
+    assert!(!nodes[1].is_hole());
+    assert!(!nodes[2].is_hole());
+    assert_eq!(nodes[2].depth, 2);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_dxf() -> Result<()> {
+    let circle = Tree::circle(1.0, TreeVec2::default());
+    let region = Region2::new(-2.0, 2.0, -2.0, 2.0);
+
+    let contours = circle.to_contour_2d::<[f32; 2]>(region, 0.0, 10.0).unwrap();
+    assert_eq!(contours.len(), 1);
+    let contour = &contours[0];
+    let vertices = contour.len() - (contour.first() == contour.last()) as usize;
+
+    let mut dxf = Vec::new();
+    write_dxf(&contours, &mut dxf).unwrap();
+    let dxf = String::from_utf8(dxf).unwrap();
+    let lines = dxf.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.iter().filter(|&&line| line == "POLYLINE").count(), 1);
+    assert_eq!(
+        lines.iter().filter(|&&line| line == "VERTEX").count(),
+        vertices
+    );
+    assert_eq!(lines.last(), Some(&"EOF"));
+
+    let path = std::env::temp_dir().join("libfive-circle.dxf");
+    circle.write_dxf(&path, &region, 0.0, 10.0)?;
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), dxf);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_svg_string() -> Result<()> {
+    let svg = Tree::circle(1.0, TreeVec2::default()).to_svg_string(
+        &Region2::new(-2.0, 2.0, -2.0, 2.0),
+        0.0,
+        10.0,
+    )?;
+
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<path").count(), 1);
+    assert!(svg.contains(r#"viewBox="-2 -2 4 4""#));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_svg_styled() -> Result<()> {
+    let ring = Tree::circle(1.0, TreeVec2::default())
+        .difference(Tree::circle(0.5, TreeVec2::default()));
+    let style = SvgStyle {
+        stroke_width: 0.05,
+        stroke: "#ff0000".to_string(),
+        fill: "blue".to_string(),
+        fill_rule: FillRule::NonZero,
+    };
+
+    let path = std::env::temp_dir().join("libfive-ring.svg");
+    ring.write_svg_styled(
+        &path,
+        &Region2::new(-2.0, 2.0, -2.0, 2.0),
+        0.0,
+        10.0,
+        &style,
+    )?;
+    let svg = std::fs::read_to_string(&path).unwrap();
+
+    assert_eq!(svg.matches("<path").count(), 1);
+    assert_eq!(svg.matches('M').count(), 2);
+    assert!(svg.contains(r##"stroke="#ff0000""##));
+    assert!(svg.contains(r#"stroke-width="0.05""#));
+    assert!(svg.contains(r#"fill="blue""#));
+    assert!(svg.contains(r#"fill-rule="nonzero""#));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_svg_layers() -> Result<()> {
+    let cone = Tree::cone_z(1.0, 1.0, TreeVec3::default());
+
+    let path = std::env::temp_dir().join("libfive-cone-layers.svg");
+    cone.write_svg_layers(
+        &path,
+        &Region2::new(-1.5, 1.5, -1.5, 1.5),
+        &[0.5, 0.1, 0.9],
+        20.0,
+    )?;
+    let svg = std::fs::read_to_string(&path).unwrap();
+
+    assert_eq!(svg.matches("<g ").count(), 3);
+    let heights = ["0.1", "0.5", "0.9"]
+        .map(|z| svg.find(&format!(r#"data-z="{}""#, z)).unwrap());
+    assert!(heights[0] < heights[1] && heights[1] < heights[2]);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_vertex_normals() {
+    let mesh = Tree::sphere(1.0, TreeVec3::default())
+        .to_triangle_mesh::<[f32; 3]>(&Region3::cube([0.0; 3], 1.5), 10.0)
+        .unwrap();
+
+    // On a sphere around the origin normals point along the positions.
+    for (position, normal) in mesh.positions.iter().zip(mesh.vertex_normals()) {
+        let length = position.iter().map(|c| c * c).sum::<f32>().sqrt();
+        let cosine = position
+            .iter()
+            .zip(normal)
+            .map(|(p, n)| p / length * n)
+            .sum::<f32>();
+        assert!(cosine > 0.9);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "gltf", feature = "stdlib"))]
+fn test_write_glb() -> Result<()> {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let region = Region3::cube([0.0; 3], 1.5);
+    let mesh = sphere.to_triangle_mesh::<[f32; 3]>(&region, 10.0).unwrap();
+
+    let path = std::env::temp_dir().join("libfive-sphere.glb");
+    sphere.write_glb(&path, &region, 10.0)?;
+
+    let document = ::gltf::Gltf::from_slice(&std::fs::read(&path).unwrap())
+        .unwrap()
+        .document;
+    assert_eq!(document.meshes().count(), 1);
+
+    let accessors = document.accessors().collect::<Vec<_>>();
+    assert_eq!(accessors[0].count(), mesh.positions.len());
+    assert_eq!(accessors[1].count(), mesh.positions.len());
+    assert_eq!(accessors[2].count(), 3 * mesh.triangles.len());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_triangles_with_positions() {
+    let mut mesh = Tree::box_exact_centered(
+        TreeVec3::new(1.0, 1.0, 1.0),
+        TreeVec3::default(),
+    )
+    .to_triangle_mesh::<[f32; 3]>(&Region3::cube([0.0; 3], 1.0), 10.0)
+    .unwrap();
+
+    let centroid = mesh
+        .triangles_with_positions()
+        .map(|[a, b, c]| [0, 1, 2].map(|i| (a[i] + b[i] + c[i]) / 3.0))
+        .fold([0.0f32; 3], |sum, c| [0, 1, 2].map(|i| sum[i] + c[i]))
+        .map(|c| c / mesh.triangles.len() as f32);
+    // The cube is centered on the origin.
+    assert!(centroid.iter().all(|c| c.abs() < 0.05));
+
+    let count = mesh.triangles.len();
+    mesh.triangles.push([0, 1, mesh.positions.len() as u32]);
+    assert_eq!(mesh.triangles_with_positions().count(), count);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_decimate() {
+    let mut mesh = FlatTriangleMesh::from(
+        Tree::sphere(1.0, TreeVec3::default())
+            .to_triangle_mesh::<[f32; 3]>(&Region3::cube([0.0; 3], 1.5), 20.0)
+            .unwrap(),
+    );
+
+    let bounds = |mesh: &FlatTriangleMesh| {
+        mesh.positions.chunks_exact(3).fold(
+            ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]),
+            |(min, max), p| {
+                (
+                    [0, 1, 2].map(|i| min[i].min(p[i])),
+                    [0, 1, 2].map(|i| max[i].max(p[i])),
+                )
+            },
+        )
+    };
+
+    let triangle_count = mesh.triangles.len() / 3;
+    let (min, max) = bounds(&mesh);
+
+    mesh.decimate(triangle_count / 4);
+
+    let decimated_count = mesh.triangles.len() / 3;
+    assert!(decimated_count <= triangle_count / 4);
+    assert!(triangle_count / 5 < decimated_count);
+    assert!(mesh
+        .triangles
+        .iter()
+        .all(|&index| (index as usize) < mesh.positions.len() / 3));
+
+    let (decimated_min, decimated_max) = bounds(&mesh);
+    for i in 0..3 {
+        assert!((decimated_min[i] - min[i]).abs() < 0.05);
+        assert!((decimated_max[i] - max[i]).abs() < 0.05);
+    }
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_fix_winding() {
+    // A hollow box: the inner faces bound a cavity and face inwards.
+    let mesh = FlatTriangleMesh::from(
+        Tree::box_exact_centered(
+            TreeVec3::new(1.0, 1.0, 1.0),
+            TreeVec3::default(),
+        )
+        .shell(0.2)
+        .to_triangle_mesh::<[f32; 3]>(&Region3::cube([0.0; 3], 1.0), 20.0)
+        .unwrap(),
+    );
+
+    let mut flipped = FlatTriangleMesh {
+        positions: mesh.positions.clone(),
+        triangles: mesh.triangles.clone(),
+    };
+    for triangle in flipped.triangles.chunks_exact_mut(3).step_by(3) {
+        triangle.swap(1, 2);
+    }
+    assert_ne!(flipped.triangles, mesh.triangles);
+
+    flipped.fix_winding();
+    assert_eq!(flipped.triangles, mesh.triangles);
+
+    // Everything facing the wrong way.
+    for triangle in flipped.triangles.chunks_exact_mut(3) {
+        triangle.swap(1, 2);
+    }
+    flipped.fix_winding();
+    assert_eq!(flipped.triangles, mesh.triangles);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_flat_triangle_mesh_round_trip() {
+    let mesh = Tree::sphere(1.0, TreeVec3::default())
+        .to_triangle_mesh::<[f32; 3]>(&Region3::cube([0.0; 3], 1.5), 10.0)
+        .unwrap();
+    let (positions, triangles) =
+        (mesh.positions.clone(), mesh.triangles.clone());
+
+    let mesh = TriangleMesh::<[f32; 3]>::from(FlatTriangleMesh::from(mesh));
+    assert_eq!(mesh.positions, positions);
+    assert_eq!(mesh.triangles, triangles);
+}
+
+#[test]
+#[should_panic(expected = "not a multiple of 3")]
+fn test_flat_triangle_mesh_invalid_length() {
+    let _ = TriangleMesh::<[f32; 3]>::from(FlatTriangleMesh {
+        positions: vec![0.0; 4],
+        triangles: Vec::new(),
+    });
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_mesh_into() -> Result<()> {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let region = Region3::cube([0.0; 3], 1.5);
+    let (mut positions, mut triangles) = (Vec::new(), Vec::new());
+
+    sphere.mesh_into(&region, 10.0, &mut positions, &mut triangles)?;
+    let mesh = FlatTriangleMesh::from(
+        sphere.to_triangle_mesh::<[f32; 3]>(&region, 10.0).unwrap(),
+    );
+    assert_eq!(positions, mesh.positions);
+    assert_eq!(triangles, mesh.triangles);
+
+    let buffers = (
+        positions.as_ptr(),
+        positions.capacity(),
+        triangles.as_ptr(),
+        triangles.capacity(),
+    );
+    sphere.mesh_into(&region, 10.0, &mut positions, &mut triangles)?;
+    assert_eq!(positions, mesh.positions);
+    assert_eq!(
+        buffers,
+        (
+            positions.as_ptr(),
+            positions.capacity(),
+            triangles.as_ptr(),
+            triangles.capacity(),
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_mesh_cache() -> Result<()> {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+    let region = Region3::cube([0.0; 3], 1.5);
+    let mut cache = MeshCache::new();
+
+    let mesh = cache.get_or_mesh(&sphere, &region, 10.0)?;
+    let cached = cache.get_or_mesh(&sphere.clone(), &region, 10.0)?;
+    assert!(std::rc::Rc::ptr_eq(&mesh, &cached));
+    assert_eq!(cache.misses(), 1);
+
+    cache.get_or_mesh(&sphere, &region, 20.0)?;
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(cache.len(), 2);
+
+    cache.clear();
+    assert!(cache.is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_region_state() {
+    let sphere = Tree::sphere(1.0, TreeVec3::default());
+
+    let outside = Region3::cube([3.0, 0.0, 0.0], 0.5);
+    assert!(sphere.eval_interval(&outside).lower > 0.0);
+    assert_eq!(sphere.region_state(&outside), RegionState::Empty);
+
+    let straddling = Region3::cube([1.0, 0.0, 0.0], 0.25);
+    let interval = sphere.eval_interval(&straddling);
+    assert!(interval.lower < 0.0 && 0.0 < interval.upper);
+    assert_eq!(sphere.region_state(&straddling), RegionState::Ambiguous);
+
+    let inside = Region3::cube([0.0; 3], 0.25);
+    assert_eq!(sphere.region_state(&inside), RegionState::Full);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_empty_result() {
+    let empty = Tree::emptiness();
+    let region = Region3::cube([0.0; 3], 1.0);
+    let path = std::env::temp_dir().join("libfive-empty.stl");
+
+    assert!(empty.to_triangle_mesh::<[f32; 3]>(&region, 10.0).is_none());
+    assert_eq!(
+        empty.write_stl(&path, &region, 10.0),
+        Err(Error::EmptyResult)
+    );
+    assert_eq!(
+        empty.write_stl_with_format(&path, &region, 10.0, StlFormat::Ascii),
+        Err(Error::EmptyResult)
+    );
+    assert_eq!(empty.to_stl_bytes(&region, 10.0), Err(Error::EmptyResult));
+
+    let (mut positions, mut triangles) = (Vec::new(), Vec::new());
+    assert_eq!(
+        empty.mesh_into(&region, 10.0, &mut positions, &mut triangles),
+        Err(Error::EmptyResult)
+    );
+
+    let region = Region2::new(-1.0, 1.0, -1.0, 1.0);
+    assert_eq!(
+        empty.write_svg(
+            std::env::temp_dir().join("libfive-empty.svg"),
+            &region,
+            0.0,
+            10.0
+        ),
+        Err(Error::EmptyResult)
+    );
+    assert_eq!(
+        empty.to_svg_string(&region, 0.0, 10.0),
+        Err(Error::EmptyResult)
+    );
+}
+
+#[test]
+fn test_invalid_region() {
+    let sphere = Tree::x().square() + Tree::y().square() + Tree::z().square()
+        - Tree::from(1.0);
+
+    assert_eq!(Region3::cube([0.0; 3], 1.5).validate(), Ok(()));
+
+    let inverted = Region3::new(1.5, -1.5, -1.5, 1.5, -1.5, 1.5);
+    assert_eq!(inverted.validate(), Err(Error::InvalidRegion));
+    assert_eq!(
+        sphere.to_stl_bytes(&inverted, 10.0),
+        Err(Error::InvalidRegion)
+    );
+
+    let flat = Region3::new(-1.5, 1.5, -1.5, 1.5, 0.0, 0.0);
+    assert_eq!(flat.validate(), Err(Error::InvalidRegion));
+    assert!(sphere.to_triangle_mesh::<[f32; 3]>(&flat, 10.0).is_none());
+
+    let region = Region2::new(-1.5, 1.5, 1.0, 1.0);
+    assert_eq!(region.validate(), Err(Error::InvalidRegion));
+    assert_eq!(
+        sphere.to_svg_string(&region, 0.0, 10.0),
+        Err(Error::InvalidRegion)
+    );
+    assert_eq!(
+        Region2::new(-1.5, f32::INFINITY, -1.5, 1.5).validate(),
+        Err(Error::InvalidRegion)
+    );
+}
+
+#[test]
+fn test_packed_opcodes() {
+    #[cfg(feature = "packed_opcodes")]
+    assert!(PACKED_OPCODES);
+    #[cfg(not(feature = "packed_opcodes"))]
+    assert!(!PACKED_OPCODES);
+}
+
+#[test]
+fn test_save_load_header() -> Result<()> {
+    let circle = Tree::x().square() + Tree::y().square() - 1.0.into();
+    let path = std::env::temp_dir().join("libfive-header.frep");
+
+    // Plain files are what `libfive` writes.
+    circle.save(&path)?;
+    let plain = std::fs::read(&path).unwrap();
+    assert!(!plain.starts_with(b"LIBFIVE\0"));
+    assert_eq!(circle.load(&path)?.eval([1.0, 0.0, 0.0]), 0.0);
+    assert_eq!(
+        circle.load_versioned(&path).err(),
+        Some(Error::FileReadFailed)
+    );
+
+    circle.save_versioned(&path)?;
+    let loaded = circle.load_versioned(&path)?;
+    assert_eq!(loaded.eval([1.0, 0.0, 0.0]), 0.0);
+
+    let data = std::fs::read(&path).unwrap();
+    assert!(data.starts_with(b"LIBFIVE\0"));
+    let length = data[13] as usize;
+    assert_eq!(&data[14..14 + length], libfive_version().as_slice());
+    assert_eq!(&data[14 + length..], plain.as_slice());
+
+    // Saved by a build with the other opcode packing.
+    let mut other = data.clone();
+    other[12] = !PACKED_OPCODES as u8;
+    std::fs::write(&path, &other).unwrap();
+    assert_eq!(
+        circle.load_versioned(&path).err(),
+        Some(Error::IncompatibleFormat {
+            version: 2,
+            packed_opcodes: !PACKED_OPCODES
+        })
+    );
+
+    let mut other = data.clone();
+    other[8] = 3;
+    std::fs::write(&path, &other).unwrap();
+    assert_eq!(
+        circle.load_versioned(&path).err(),
+        Some(Error::IncompatibleFormat {
+            version: 3,
+            packed_opcodes: PACKED_OPCODES
+        })
+    );
+
+    // The version of `libfive` is only recorded, not checked.
+    let mut other = data.clone();
+    other[14..14 + length].fill(b'?');
+    std::fs::write(&path, &other).unwrap();
+    assert_eq!(circle.load_versioned(&path)?.eval([1.0, 0.0, 0.0]), 0.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_rounding() {
+    let sample =
+        |tree: Tree| [-1.5, 0.5, 2.0].map(|x| tree.eval([x, 0.0, 0.0]));
+
+    assert_eq!(sample(Tree::x().floor()), [-2.0, 0.0, 2.0]);
+    assert_eq!(sample(Tree::x().ceil()), [-1.0, 1.0, 2.0]);
+    assert_eq!(sample(Tree::x().round()), [-1.0, 1.0, 2.0]);
+    assert_eq!(sample(Tree::x().fract()), [0.5, 0.5, 0.0]);
+}
+
+#[test]
+fn test_sign_copysign() {
+    let sample =
+        |tree: Tree| [-2.5, 0.0, 0.5].map(|x| tree.eval([x, 0.0, 0.0]));
+
+    assert_eq!(sample(Tree::x().sign()), [-1.0, 0.0, 1.0]);
+    assert_eq!(
+        sample(Tree::from(3.0).copysign(Tree::x())),
+        [-3.0, 3.0, 3.0]
+    );
+    assert_eq!(
+        sample(Tree::from(-3.0).copysign(Tree::x())),
+        [-3.0, 3.0, 3.0]
+    );
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_polar_xy() {
+    // A stripe around the angle `0` in polar space.
+    let spoke = (Tree::y().abs() - Tree::from(0.1)).to_polar_xy();
+    assert!(spoke.eval([2.0, 0.0, 0.0]) < 0.0);
+    assert!(spoke.eval([5.0, 0.2, 0.0]) < 0.0);
+    assert!(spoke.eval([-2.0, 0.0, 0.0]) > 0.0);
+    assert!(spoke.eval([0.0, 2.0, 0.0]) > 0.0);
+    assert!(spoke.eval([2.0, 1.0, 0.0]) > 0.0);
+
+    let circle = Tree::circle(1.0, TreeVec2::default());
+    let unwrapped = circle.clone().from_polar_xy();
+    for angle in [-3.0, 0.0, 1.0, 3.0] {
+        assert!(unwrapped.eval([0.5, angle, 0.0]) < 0.0);
+        assert!(unwrapped.eval([1.5, angle, 0.0]) > 0.0);
+    }
+
+    let round_trip = circle.clone().from_polar_xy().to_polar_xy();
+    for point in [[0.3, -0.4, 0.0], [1.2, 0.5, 0.0], [-0.7, 0.1, 0.0]] {
+        assert!((round_trip.eval(point) - circle.eval(point)).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_safe_div() {
+    let length = (Tree::x().square() + Tree::y().square()).sqrt();
+    let cosine = Tree::x().safe_div(length, 2.0);
+
+    assert_eq!(cosine.eval([0.0; 3]), 2.0);
+    assert_eq!(cosine.eval([3.0, 4.0, 0.0]), 0.6);
+    assert_eq!(
+        Tree::from(1.0).safe_div(Tree::x(), 2.0).eval([0.0; 3]),
+        f32::INFINITY
+    );
+}
+
+#[test]
+fn test_angles() {
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+    assert_eq!(Tree::pi().as_f32(), Ok(PI));
+    assert_eq!(Tree::tau().as_f32(), Ok(TAU));
+    assert!((Tree::radians(180.0).as_f32().unwrap() - PI).abs() < 1e-6);
+    assert!((Tree::degrees(FRAC_PI_2).as_f32().unwrap() - 90.0).abs() < 1e-4);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_csg_macro() {
+    let sphere = |r: f32| Tree::sphere(r, TreeVec3::default());
+    let cylinder = || Tree::cylinder_z(0.4, 4.0, TreeVec3::new(0.0, 0.0, -2.0));
+
+    let macro_built = csg! {
+        difference {
+            union {
+                sphere(1.0),
+                sphere(0.5).moveit(TreeVec3::new(1.0, 0.0, 0.0)),
+            },
+            sphere(0.6),
+            intersection { cylinder(), cylinder().reflect_xz() },
+        }
+    };
+    let method_built = sphere(1.0)
+        .union_multi([sphere(0.5).moveit(TreeVec3::new(1.0, 0.0, 0.0))])
+        .difference_multi([
+            sphere(0.6),
+            cylinder().intersection_multi([cylinder().reflect_xz()]),
+        ]);
+
+    let region = Region3::cube([0.0; 3], 2.0);
+    let macro_mesh = macro_built
+        .to_triangle_mesh::<[f32; 3]>(&region, 8.0)
+        .unwrap();
+    let method_mesh = method_built
+        .to_triangle_mesh::<[f32; 3]>(&region, 8.0)
+        .unwrap();
+    assert_eq!(macro_mesh.positions, method_mesh.positions);
+    assert_eq!(macro_mesh.triangles, method_mesh.triangles);
+
+    assert_eq!(macro_built.to_sexpr(), method_built.to_sexpr());
+    assert_eq!(csg! { union { sphere(1.0) } }.eval([0.0; 3]), -1.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_parametric() -> Result<()> {
+    let mut parametric = Parametric::new();
+    let inner_radius = parametric.var("inner_radius", 0.6)?;
+    assert_eq!(
+        parametric.var("inner_radius", 0.6).err(),
+        Some(Error::VariableAlreadyAdded)
+    );
+
+    let csg_shape =
+        Tree::sphere(1.0, TreeVec3::default()).difference_multi(vec![
+            Tree::sphere(inner_radius, TreeVec3::default()),
+            Tree::cylinder_z(0.6, 2.0, TreeVec3::new(0.0, 0.0, -1.0)),
+        ]);
+    let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+
+    let thick = parametric.mesh::<[f32; 3]>(&csg_shape, &region)?;
+
+    parametric.set("inner_radius", 0.9)?;
+    assert_eq!(parametric.get("inner_radius"), Some(0.9));
+    let thin = parametric.mesh::<[f32; 3]>(&csg_shape, &region)?;
+
+    assert_ne!(thick.triangles.len(), thin.triangles.len());
+    assert_eq!(
+        parametric.set("outer_radius", 1.0),
+        Err(Error::VariableNotFound)
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_parametric_remesh() -> Result<()> {
+    let mut parametric = Parametric::new();
+    let radius = parametric.var("radius", 1.0)?;
+    let sphere = Tree::sphere(radius, TreeVec3::default());
+    let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+
+    let max_radius = |mesh: &TriangleMesh<[f32; 3]>| {
+        mesh.positions
+            .iter()
+            .map(|p| p[0].hypot(p[1]).hypot(p[2]))
+            .fold(0.0, f32::max)
+    };
+
+    let small = parametric.mesh::<[f32; 3]>(&sphere, &region)?;
+    // The second and third meshing re-use the evaluator.
+    parametric.set("radius", 1.5)?;
+    let large = parametric.mesh::<[f32; 3]>(&sphere, &region)?;
+    parametric.set("radius", 0.5)?;
+    let tiny = parametric.mesh::<[f32; 3]>(&sphere, &region)?;
+
+    assert_ne!(small.positions, large.positions);
+    for (mesh, radius) in [(&small, 1.0), (&large, 1.5), (&tiny, 0.5)] {
+        assert!((max_radius(mesh) - radius).abs() < 0.1);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_evaluator_eval_many() -> Result<()> {
+    let mut variables = Variables::new();
+    let radius = variables.add("radius", 1.0)?;
+
+    let sphere = Tree::sphere(radius, TreeVec3::default());
+    let mut evaluator = Evaluator::new(&sphere, &variables);
+
+    let points = [[0.0, 0.0, 0.0], [0.0, 1.2, 0.0], [1.8, 0.0, 0.0]];
+    let small = evaluator.eval_many(&points);
+
+    variables.set("radius", 1.5)?;
+    evaluator.update(&variables)?;
+    let large = evaluator.eval_many(&points);
+
+    for (values, radius) in [(&small, 1.0), (&large, 1.5)] {
+        for (value, point) in values.iter().zip(&points) {
+            let expected = point[0].hypot(point[1]) - radius;
+            assert!((value - expected).abs() < 1.0e-5);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_shared_tree() {
+    let ball = Tree::sphere(1.0, TreeVec3::default()).shared();
+
+    // A shell between radius 0.5 and 1 around a core of radius 0.2.
+    let shape = ball.intersection(
+        &ball
+            .difference(&(&ball + Tree::from(0.5)))
+            .union(&ball + Tree::from(0.8)),
+    );
+
+    assert!(shape.eval([0.0; 3]) < 0.0);
+    assert!(shape.eval([0.35, 0.0, 0.0]) > 0.0);
+    assert!(shape.eval([0.0, 0.75, 0.0]) < 0.0);
+    assert!(shape.eval([0.0, 0.0, 1.2]) > 0.0);
+
+    let mesh = shape
+        .to_triangle_mesh::<[f32; 3]>(&Region3::cube([0.0; 3], 1.5), 20.0)
+        .unwrap();
+    assert!(!mesh.triangles.is_empty());
+    for position in &mesh.positions {
+        let radius = position[0].hypot(position[1]).hypot(position[2]);
+        assert!([0.2, 0.5, 1.0]
+            .iter()
+            .any(|expected| (radius - expected).abs() < 0.05));
+    }
+
+    let ball = ball.into_tree();
+    assert_eq!(ball.eval([0.0; 3]), -1.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_remap_translation() {
+    let offset = [0.5, -0.25, 1.0];
+    let sphere = || Tree::sphere(1.0, TreeVec3::default());
+
+    let remapped = sphere().remap(
+        Tree::x() - offset[0].into(),
+        Tree::y() - offset[1].into(),
+        Tree::z() - offset[2].into(),
+    );
+    let moved = sphere().moveit(TreeVec3::new(offset[0], offset[1], offset[2]));
+
+    for point in [
+        [0.0, 0.0, 0.0],
+        [0.5, -0.25, 1.0],
+        [1.5, -0.25, 1.0],
+        [-1.0, 2.0, 0.5],
+    ] {
+        assert!((remapped.eval(point) - moved.eval(point)).abs() < 1e-6);
+    }
+
+    let region = Region3::cube([0.0; 3], 2.5);
+    let remapped_mesh =
+        remapped.to_triangle_mesh::<[f32; 3]>(&region, 8.0).unwrap();
+    let moved_mesh = moved.to_triangle_mesh::<[f32; 3]>(&region, 8.0).unwrap();
+    assert_eq!(remapped_mesh.positions, moved_mesh.positions);
+    assert_eq!(remapped_mesh.triangles, moved_mesh.triangles);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_shear_taper_axes() {
+    let ball = |center: [f32; 3]| {
+        Tree::sphere(0.5, TreeVec3::new(center[0], center[1], center[2]))
+    };
+    // Each shear shifts by the driving coordinate: `0` at `0`, `1` at `1`.
+    let sheared =
+        ball([2.0, 0.0, 0.0]).shear_y_x(TreeVec2::default(), 1.0, 1.0, 0.0);
+    assert!(sheared.eval([2.0, 2.0, 0.0]) < 0.0);
+    assert!(sheared.eval([2.0, 0.0, 0.0]) > 0.0);
+
+    for (shear, center, moved) in [
+        (
+            Tree::shear_x_z as fn(_, _, _, _, _) -> _,
+            [0.0, 0.0, 2.0],
+            [2.0, 0.0, 2.0],
+        ),
+        (Tree::shear_z_x, [2.0, 0.0, 0.0], [2.0, 0.0, 2.0]),
+        (Tree::shear_y_z, [0.0, 0.0, 2.0], [0.0, 2.0, 2.0]),
+        (Tree::shear_z_y, [0.0, 2.0, 0.0], [0.0, 2.0, 2.0]),
+    ] {
+        let sheared = shear(
+            ball(center),
+            TreeVec3::default(),
+            1.0.into(),
+            1.0.into(),
+            0.0.into(),
+        );
+        assert!(sheared.eval(moved) < 0.0);
+        assert!(sheared.eval(center) > 0.0);
+    }
+
+    // Doubles the width from `x = 0` to `x = 1`.
+    let slab = (Tree::y().abs() - 1.0.into()).taper_y_x(
+        TreeVec2::default(),
+        1.0,
+        2.0,
+        1.0,
+    );
+    assert!(slab.eval([1.0, 1.5, 0.0]) < 0.0);
+    assert!(slab.eval([0.0, 1.5, 0.0]) > 0.0);
+
+    let rod = ((Tree::y().square() + Tree::z().square()).sqrt() - 1.0.into())
+        .taper_yz_x(TreeVec3::default(), 1.0, 2.0, 1.0);
+    assert!(rod.eval([1.0, 0.0, 1.5]) < 0.0);
+    assert!(rod.eval([1.0, 1.5, 0.0]) < 0.0);
+    assert!(rod.eval([0.0, 0.0, 1.5]) > 0.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_rounded_chamfered_box() {
+    let size = || TreeVec3::new(2.0, 2.0, 2.0);
+
+    let rounded = Tree::rounded_box_centered(size(), TreeVec3::default(), 0.5);
+    assert!((rounded.eval([0.0; 3]) + 1.0).abs() < 1e-6);
+    assert!(rounded.eval([0.99, 0.0, 0.0]) < 0.0);
+    assert!(rounded.eval([0.0, 0.0, -0.99]) < 0.0);
+    // Within `r` of the sharp corner & edge.
+    assert!(rounded.eval([0.9, 0.9, 0.9]) > 0.0);
+    assert!(rounded.eval([-0.9, 0.9, 0.0]) > 0.0);
+    // On the rounded corner.
+    let corner = 0.5 + 0.5 / 3.0f32.sqrt();
+    assert!(rounded.eval([corner; 3]).abs() < 1e-5);
+
+    let chamfered = Tree::chamfered_box(size(), TreeVec3::default(), 0.4);
+    assert!(chamfered.eval([0.0; 3]) < 0.0);
+    assert!(chamfered.eval([0.99, 0.0, 0.0]) < 0.0);
+    assert!(chamfered.eval([0.9, 0.9, 0.0]) > 0.0);
+    // The bevel along the `z` edge at `x, y > 0` is the plane
+    // `x + y = 1.6`.
+    for point in [[0.9, 0.7, 0.0], [0.8, 0.8, 0.5], [0.7, 0.9, -0.3]] {
+        assert!(chamfered.eval(point).abs() < 1e-5);
+        let gradient = chamfered.gradient(point);
+        let expected = [core::f32::consts::FRAC_1_SQRT_2; 2];
+        assert!((gradient[0] - expected[0]).abs() < 1e-5);
+        assert!((gradient[1] - expected[1]).abs() < 1e-5);
+        assert!(gradient[2].abs() < 1e-5);
+    }
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_torus_axes() {
+    let center = [1.0, -2.0, 0.5];
+    let offset =
+        |point: [f32; 3]| [0, 1, 2].map(|axis| center[axis] + point[axis]);
+
+    for (torus, axis) in [
+        (Tree::torus_x as fn(_, _, _) -> _, 0),
+        (Tree::torus_y, 1),
+        (Tree::torus_z, 2),
+    ] {
+        let torus = torus(
+            1.0.into(),
+            0.25.into(),
+            TreeVec3::new(center[0], center[1], center[2]),
+        );
+
+        let mut along = [0.0; 3];
+        along[axis] = 1.0;
+        // The hole: the center and points along the axis are outside.
+        assert!(torus.eval(offset([0.0; 3])) > 0.0);
+        assert!(torus.eval(offset(along)) > 0.0);
+
+        // The tube: all points at the major radius around the axis are
+        // inside.
+        for other in (0..3).filter(|&other| other != axis) {
+            for sign in [-1.0, 1.0] {
+                let mut point = [0.0; 3];
+                point[other] = sign;
+                assert!(torus.eval(offset(point)) < 0.0);
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_rounded_polygon_star() {
+    use std::f32::consts::{PI, TAU};
+
+    let n = 5;
+    let center = [0.5, -1.0];
+    let at = |radius: f32, angle: f32| {
+        [
+            center[0] + radius * angle.cos(),
+            center[1] + radius * angle.sin(),
+            0.0,
+        ]
+    };
+
+    let star = Tree::star(2.0, 1.0, n, TreeVec2::new(center[0], center[1]));
+    // A circle between the radii crosses the outline at each edge.
+    let crossings = (0..720)
+        .map(|step| star.eval(at(1.5, step as f32 * TAU / 720.0)) < 0.0)
+        .collect::<Vec<_>>();
+    let changes = (0..crossings.len())
+        .filter(|&i| crossings[i] != crossings[(i + 1) % crossings.len()])
+        .count();
+    assert_eq!(changes, 2 * n as usize);
+    for k in 0..n {
+        let angle = k as f32 * TAU / n as f32;
+        assert!(star.eval(at(2.0, angle)).abs() < 1e-5);
+        assert!(star.eval(at(1.0, angle + PI / n as f32)).abs() < 1e-5);
+    }
+    assert!(star.eval(at(0.0, 0.0)) < 0.0);
+
+    let rounded =
+        Tree::rounded_polygon(2.0, n, 0.3, TreeVec2::new(center[0], center[1]));
+    let apothem = 2.0 * (PI / n as f32).cos();
+    // The sides stay in place while the corners are cut off.
+    assert!(rounded.eval(at(apothem, PI / n as f32)).abs() < 1e-5);
+    assert!(rounded.eval(at(1.95, 0.0)) > 0.0);
+    // No sharp corners: the gradient stays a unit vector and turns
+    // smoothly around each corner.
+    let mut previous: Option<[f32; 3]> = None;
+    for step in 0..=40 {
+        let angle = -0.4 + step as f32 * 0.02;
+        let gradient = rounded.gradient(at(2.2, angle));
+        let length = gradient[0].hypot(gradient[1]);
+        assert!((length - 1.0).abs() < 1e-3);
+        if let Some(previous) = previous {
+            let turn = (previous[0] * gradient[0] + previous[1] * gradient[1])
+                .clamp(-1.0, 1.0)
+                .acos();
+            assert!(turn < 0.2);
+        }
+        previous = Some(gradient);
+    }
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_fullness() {
+    let full = Tree::fullness();
+    for point in [[0.0; 3], [1e6, -1e6, 3.0], [-0.5, 0.25, 1e-6]] {
+        assert!(full.eval(point) < 0.0);
+    }
+    assert!(Tree::intersection_all([]).eval([0.0; 3]) < 0.0);
+
+    let sphere = || Tree::sphere(1.0, TreeVec3::default());
+    let region = Region3::cube([0.0; 3], 1.5);
+    let intersected = Tree::fullness()
+        .intersection(sphere())
+        .to_triangle_mesh::<[f32; 3]>(&region, 10.0)
+        .unwrap();
+    let plain = sphere()
+        .to_triangle_mesh::<[f32; 3]>(&region, 10.0)
+        .unwrap();
+    assert_eq!(intersected.positions, plain.positions);
+    assert_eq!(intersected.triangles, plain.triangles);
+}
+
+#[test]
+fn test_tree_debug() {
+    let tree = Tree::x().square() + Tree::y().square();
+    let debug = format!("{:?}", tree);
+
+    assert_eq!(debug, format!("Tree {{ id: {}, op: Add }}", tree.id()));
+
+    let huge = (0..100).fold(Tree::x(), |tree, _| tree.sin());
+    let debug = format!("{:?}", huge);
+    assert_eq!(debug, format!("Tree {{ id: {}, op: Sin }}", huge.id()));
+}
+
+#[test]
+fn test_flat_mesh_write() {
+    // A tetrahedron.
+    let mesh = FlatTriangleMesh {
+        positions: vec![
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0,
+        ],
+        triangles: vec![0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3],
+    };
+    let (vertex_count, triangle_count) = (4, 4);
+
+    let write = |format| {
+        let mut data = Vec::new();
+        mesh.write(&mut data, format).unwrap();
+        assert!(!data.is_empty());
+        data
+    };
+
+    let stl = write(MeshFormat::Stl);
+    assert_eq!(stl.len(), 84 + 50 * triangle_count);
+    assert_eq!(
+        u32::from_le_bytes(stl[80..84].try_into().unwrap()),
+        triangle_count as u32
+    );
+
+    let obj = String::from_utf8(write(MeshFormat::Obj)).unwrap();
+    let lines = |prefix: &'static str| {
+        obj.lines()
+            .filter_map(move |line| line.strip_prefix(prefix))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+    };
+    assert_eq!(lines("v ").count(), vertex_count);
+    assert_eq!(lines("f ").count(), triangle_count);
+    for vertex in lines("v ") {
+        assert_eq!(vertex.len(), 3);
+        assert!(vertex.iter().all(|value| value.parse::<f32>().is_ok()));
+    }
+    for face in lines("f ") {
+        assert_eq!(face.len(), 3);
+        assert!(face.iter().all(|index| {
+            (1..=vertex_count).contains(&index.parse::<usize>().unwrap())
+        }));
+    }
+
+    let ply = write(MeshFormat::Ply);
+    let end = b"end_header\n";
+    let header_length = ply
+        .windows(end.len())
+        .position(|window| window == end)
+        .unwrap()
+        + end.len();
+    let header = std::str::from_utf8(&ply[..header_length]).unwrap();
+    assert!(header.starts_with("ply\nformat binary_little_endian 1.0\n"));
+    assert!(header.contains(&format!("element vertex {}\n", vertex_count)));
+    assert!(header.contains(&format!("element face {}\n", triangle_count)));
+    assert_eq!(
+        ply.len(),
+        header_length + 12 * vertex_count + 13 * triangle_count
+    );
+
+    #[cfg(feature = "gltf")]
+    assert!(::gltf::Gltf::from_slice(&write(MeshFormat::Gltf)).is_ok());
+
+    let broken = FlatTriangleMesh {
+        positions: vec![0.0; 9],
+        triangles: vec![0, 1, 3],
+    };
+    assert_eq!(
+        broken
+            .write(Vec::new(), MeshFormat::Obj)
+            .unwrap_err()
+            .kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_array_polar_z_offset() {
+    let (n, radius, start_angle, center) = (5, 2.0f32, 0.3f32, [1.0, -0.5]);
+    let array = Tree::array_polar_z_offset(
+        Tree::sphere(0.1, TreeVec3::default()),
+        n,
+        radius,
+        start_angle,
+        TreeVec2::new(center[0], center[1]),
+    );
+
+    let on_circle = |distance: f32, angle: f32| {
+        [
+            center[0] + distance * angle.cos(),
+            center[1] + distance * angle.sin(),
+            0.0,
+        ]
+    };
+    for i in 0..n {
+        let angle = start_angle + i as f32 * std::f32::consts::TAU / n as f32;
+        assert!(array.eval(on_circle(radius, angle)) < 0.0);
+        assert!(array.eval(on_circle(radius - 0.2, angle)) > 0.0);
+        assert!(array.eval(on_circle(radius + 0.2, angle)) > 0.0);
+        // Half way between two copies.
+        let between = angle + std::f32::consts::PI / n as f32;
+        assert!(array.eval(on_circle(radius, between)) > 0.0);
+    }
+    assert!(array.eval([center[0], center[1], 0.0]) > 0.0);
+}
+
+#[test]
+fn test_less_greater_than() {
+    let less = Tree::x().less_than(1.0.into());
+    let greater = Tree::x().greater_than(1.0.into());
+
+    for x in [-10.0, 0.0, 0.5, 0.999, 1.0, 1.001, 2.0, 10.0] {
+        let (expected_less, expected_greater) = match x {
+            x if x < 1.0 => (1.0, 0.0),
+            x if x > 1.0 => (0.0, 1.0),
+            _ => (0.0, 0.0),
+        };
+        assert_eq!(less.eval([x, 0.0, 0.0]), expected_less, "x = {}", x);
+        assert_eq!(greater.eval([x, 0.0, 0.0]), expected_greater, "x = {}", x);
+    }
+
+    // Between two fields.
+    let below = Tree::y().less_than(Tree::x());
+    assert_eq!(below.eval([1.0, 0.5, 0.0]), 1.0);
+    assert_eq!(below.eval([0.5, 1.0, 0.0]), 0.0);
+}
+
+#[test]
+fn test_select() {
+    let mask = Tree::x() - Tree::from(0.5);
+    let field = Tree::select(mask, 2.0.into(), (-7.0).into());
+
+    for x in [-1.0, 0.0, 0.49, 0.5] {
+        assert_eq!(field.eval([x, 0.0, 0.0]), -7.0, "x = {}", x);
+    }
+    for x in [0.51, 1.0, 10.0] {
+        assert_eq!(field.eval([x, 0.0, 0.0]), 2.0, "x = {}", x);
+    }
+
+    // Fields as branches.
+    let field = Tree::select(Tree::y(), Tree::x(), Tree::z());
+    assert_eq!(field.eval([1.0, 1.0, 3.0]), 1.0);
+    assert_eq!(field.eval([1.0, -1.0, 3.0]), 3.0);
+}
+
+#[test]
+fn test_to_bitmap_checked() {
+    let region = Region2::new(-2.0, 2.0, -2.0, 2.0);
+
+    let circle = Tree::x().square() + Tree::y().square() - 1.0.into();
+    let bitmap = circle.to_bitmap_checked(&region, 0.0, 10.0).unwrap();
+    // `true` is inside.
+    assert!(bitmap.pixel(bitmap.width() / 2, bitmap.height() / 2));
+    assert!(!bitmap.pixel(0, 0));
+
+    let empty = Tree::from(1.0);
+    assert!(matches!(
+        empty.to_bitmap_checked(&region, 0.0, 10.0),
+        Err(Error::EmptyResult)
+    ));
+    // The infallible version still returns the blank bitmap.
+    assert!(!empty
+        .to_bitmap(&region, 0.0, 10.0)
+        .as_slice()
+        .iter()
+        .any(|&occupied| occupied));
+
+    assert!(matches!(
+        circle.to_bitmap_checked(&region, 0.0, 0.0),
+        Err(Error::InvalidResolution)
+    ));
+}
+
+#[test]
+fn test_region_union_intersection() {
+    let a = Region3::new(0.0, 2.0, 0.0, 2.0, 0.0, 2.0);
+    let b = Region3::new(1.0, 3.0, -1.0, 1.0, 0.5, 1.5);
+
+    let union = a.union(&b);
+    assert_eq!(union, Region3::new(0.0, 3.0, -1.0, 2.0, 0.0, 2.0));
+    assert_eq!(union, b.union(&a));
+    assert_eq!(
+        a.intersection(&b),
+        Some(Region3::new(1.0, 2.0, 0.0, 1.0, 0.5, 1.5))
+    );
+
+    let disjoint = Region3::new(5.0, 6.0, 0.0, 2.0, 0.0, 2.0);
+    assert_eq!(a.intersection(&disjoint), None);
+    assert_eq!(
+        a.union(&disjoint),
+        Region3::new(0.0, 6.0, 0.0, 2.0, 0.0, 2.0)
+    );
+    // Touching regions have no overlapping volume.
+    let touching = Region3::new(2.0, 3.0, 0.0, 2.0, 0.0, 2.0);
+    assert_eq!(a.intersection(&touching), None);
+
+    assert!(a.contains_point([1.0, 1.0, 1.0]));
+    assert!(a.contains_point([2.0, 0.0, 1.0]));
+    assert!(!a.contains_point([1.0, 1.0, 2.5]));
+
+    let a = Region2::new(0.0, 2.0, 0.0, 2.0);
+    let b = Region2::new(1.0, 3.0, -1.0, 1.0);
+    assert_eq!(a.union(&b), Region2::new(0.0, 3.0, -1.0, 2.0));
+    assert_eq!(a.intersection(&b), Some(Region2::new(1.0, 2.0, 0.0, 1.0)));
+    assert_eq!(a.intersection(&Region2::new(-3.0, -1.0, 0.0, 2.0)), None);
+    assert!(a.contains_point([0.0, 2.0]));
+    assert!(!a.contains_point([-0.5, 1.0]));
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_grid_layout() {
+    // Seven unit spheres in three columns, i.e. two full rows and one with
+    // a single sphere.
+    let spheres = (0..7)
+        .map(|_| Tree::sphere(1.0, TreeVec3::default()))
+        .collect();
+    let grid = Tree::grid_layout(spheres, 3, TreeVec2::new(3.0, 4.0));
+
+    let bounds = grid
+        .bounds(&Region3::new(-10.0, 20.0, -10.0, 20.0, -10.0, 10.0))
+        .unwrap();
+    let expected = [[-1.0, 7.0], [-1.0, 9.0], [-1.0, 1.0]];
+    for axis in 0..3 {
+        // `bounds()` may be larger by 1/64th of the hint's size.
+        assert!((bounds.min()[axis] - expected[axis][0]).abs() < 0.5);
+        assert!((bounds.max()[axis] - expected[axis][1]).abs() < 0.5);
+    }
+
+    // The last row only has a sphere in the first column.
+    assert!(grid.eval([0.0, 8.0, 0.0]) < 0.0);
+    assert!(grid.eval([3.0, 8.0, 0.0]) > 0.0);
+    assert!(grid.eval([6.0, 4.0, 0.0]) < 0.0);
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_symmetric_at() {
+    let feature = || Tree::sphere(0.5, TreeVec3::new(3.0, 3.0, 3.0));
+    let plane = 2.0;
+
+    // Each copy is mirrored from `3` to `2 * 2 - 3 = 1` along one axis.
+    let symmetric = [
+        feature().symmetric_x_at(plane),
+        feature().symmetric_y_at(plane),
+        feature().symmetric_z_at(plane),
+    ];
+    for (axis, tree) in symmetric.iter().enumerate() {
+        let mut mirrored = [3.0; 3];
+        mirrored[axis] = 1.0;
+        assert!(tree.eval([3.0; 3]) < 0.0);
+        assert!(tree.eval(mirrored) < 0.0);
+
+        // Mirrored about the plane, not the origin.
+        let mut about_origin = [3.0; 3];
+        about_origin[axis] = -3.0;
+        assert!(tree.eval(about_origin) > 0.0);
+
+        // Distances are mirrored exactly.
+        let mut probe = [3.2, 3.1, 2.7];
+        let expected = tree.eval(probe);
+        probe[axis] = 2.0 * plane - probe[axis];
+        assert!((tree.eval(probe) - expected).abs() < 1e-5);
+    }
+}
+
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_union_intersection_euclidean() {
+    // Half-spaces meeting at a right angle along the `z` axis.
+    let (a, b) = (Tree::x(), Tree::y());
+    let cheap_union = a.clone().union(b.clone());
+    let euclidean_union = a.clone().union_euclidean(b.clone());
+    let cheap_intersection = a.clone().intersection(b.clone());
+    let euclidean_intersection = a.intersection_euclidean(b);
+
+    let magnitude = |tree: &Tree, point| {
+        tree.gradient(point)
+            .iter()
+            .map(|g| g * g)
+            .sum::<f32>()
+            .sqrt()
+    };
+
+    // Inside the union, near the joint. The true distance to the surface
+    // is the one to the `z` axis.
+    let point = [-1.0, -1.0, 0.0];
+    assert!((euclidean_union.eval(point) + 2.0f32.sqrt()).abs() < 1e-5);
+    assert!((cheap_union.eval(point) + 1.0).abs() < 1e-5);
+    // Both are still proper distance fields there, with unit gradients.
+    assert!(
+        (magnitude(&euclidean_union, [-1.0, -0.5, 0.0]) - 1.0).abs() < 1e-3
+    );
+    assert!((magnitude(&cheap_union, [-1.0, -0.5, 0.0]) - 1.0).abs() < 1e-3);
+
+    // Outside the intersection, near the edge.
+    let point = [1.0, 1.0, 0.0];
+    assert!((euclidean_intersection.eval(point) - 2.0f32.sqrt()).abs() < 1e-5);
+    assert!((cheap_intersection.eval(point) - 1.0).abs() < 1e-5);
+    assert!(
+        (magnitude(&euclidean_intersection, [1.0, 0.5, 0.0]) - 1.0).abs()
+            < 1e-3
+    );
+
+    // Away from the joint and on the surface both variants agree.
+    for point in [[-1.0, 3.0, 0.0], [3.0, -1.0, 0.0], [0.0, 2.0, 0.0]] {
+        assert!(
+            (euclidean_union.eval(point) - cheap_union.eval(point)).abs()
+                < 1e-5
+        );
+    }
+    for point in [[-1.0, -3.0, 0.0], [-3.0, 1.0, 0.0], [0.0, -2.0, 0.0]] {
+        assert!(
+            (euclidean_intersection.eval(point)
+                - cheap_intersection.eval(point))
+            .abs()
+                < 1e-5
+        );
+    }
+
+    // Overlapping spheres do not meet at right angles. At the center of
+    // coincident ones the Euclidean variants overestimate the true distance
+    // of 1 by a factor of √2.
+    let sphere = || Tree::sphere(1.0, TreeVec3::default());
+    let union = sphere().union_euclidean(sphere());
+    assert!((union.eval([0.0; 3]) + 2.0f32.sqrt()).abs() < 1e-5);
+    assert!((sphere().union(sphere()).eval([0.0; 3]) + 1.0).abs() < 1e-5);
+
+    let intersection = Tree::x().intersection_euclidean(Tree::x());
+    assert!((intersection.eval([1.0, 0.0, 0.0]) - 2.0f32.sqrt()).abs() < 1e-5);
+
+    // Spheres overlapping halfway. The surface closest to their common
+    // center is the circle where they intersect, √0.75 away.
+    let a = Tree::sphere(1.0, TreeVec3::new(-0.5, 0.0, 0.0));
+    let b = Tree::sphere(1.0, TreeVec3::new(0.5, 0.0, 0.0));
+    let value = a.union_euclidean(b).eval([0.0; 3]);
+    assert!(value < 0.0);
+    assert!((value + 0.75f32.sqrt()).abs() < 0.2);
+}
+
+#[test]
+fn test_to_triangle_mesh_adaptive() {
+    // A slab 0.02 units thick, between the samples of coarse grids.
+    let slab = (Tree::z() - Tree::from(0.21)).abs() - Tree::from(0.01);
+    let region = Region3::new(-0.25, 0.25, -0.25, 0.25, -0.5, 0.5);
+
+    assert!(slab.to_triangle_mesh::<[f32; 3]>(&region, 4.0).is_none());
+
+    let mesh = slab
+        .to_triangle_mesh_adaptive::<[f32; 3]>(&region, 0.02)
+        .unwrap();
+    assert!(!mesh.triangles.is_empty());
+    assert!(mesh
+        .positions
+        .iter()
+        .all(|position| (0.19..=0.23).contains(&position[2])));
+
+    assert_eq!(
+        slab.to_triangle_mesh_adaptive::<[f32; 3]>(&region, 0.0)
+            .err(),
+        Some(Error::InvalidResolution)
+    );
+}
+
+#[test]
+fn test_mesh_streaming() {
+    let sphere = (Tree::x().square() + Tree::y().square() + Tree::z().square())
+        .sqrt()
+        - Tree::from(1.0);
+    let region = Region3::cube([0.0; 3], 1.5);
+
+    let mesh = sphere.to_triangle_mesh::<[f32; 3]>(&region, 8.0).unwrap();
+
+    let mut streamed = Vec::new();
+    sphere
+        .mesh_streaming(&region, &MeshOptions::new(8.0), |triangle| {
+            streamed.push(triangle)
+        })
+        .unwrap();
+
+    assert_eq!(streamed.len(), mesh.triangles.len());
+    for (triangle, indices) in streamed.iter().zip(&mesh.triangles) {
+        assert_eq!(
+            *triangle,
+            indices.map(|index| mesh.positions[index as usize])
+        );
+    }
+
+    let options = MeshOptions::new(8.0);
+    options.cancel.store(true, Ordering::Relaxed);
+    let mut count = 0;
+    assert_eq!(
+        sphere.mesh_streaming(&region, &options, |_| count += 1),
+        Err(Error::Cancelled)
+    );
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_save_scheme() -> Result<()> {
+    let path = std::env::temp_dir().join("libfive-save-scheme.scm");
+
+    let circle =
+        (Tree::x().square() + Tree::y().square()).sqrt() - Tree::from(1.0);
+    circle.save_scheme(&path)?;
+    let scheme = std::fs::read_to_string(&path).unwrap();
+
+    assert!(scheme.contains("(lambda-shape (x y z)"));
+    for name in ["sqrt", "square", "+", "-"] {
+        assert!(scheme.contains(&format!("({} ", name)), "{}", name);
+    }
+    // The expression is the one `libfive` prints, wrapped.
+    assert!(scheme.contains(&circle.to_sexpr()));
+    assert_eq!(scheme.matches('(').count(), scheme.matches(')').count());
+
+    let angle = Tree::y().atan2(Tree::x()).nan_fill(f32::INFINITY.into());
+    angle.save_scheme(&path)?;
+    let scheme = std::fs::read_to_string(&path).unwrap();
+    assert!(scheme.contains("(atan y x)"));
+    assert!(scheme.contains("(nan-fill "));
+    assert!(scheme.contains("+inf.0"));
+    assert!(!scheme.contains("atan2"));
+
+    let mut variables = Variables::new();
+    let radius = variables.add("radius", 1.0)?;
+    assert_eq!(
+        (Tree::x() - radius).save_scheme(&path),
+        Err(Error::TreeHasVariables)
     );
 
+    let _ = std::fs::remove_file(&path);
+
     Ok(())
 }
 
 #[test]
 #[cfg(feature = "stdlib")]
-fn test_3d() -> Result<()> {
-    let f_rep_shape = Tree::sphere(1.0.into(), TreeVec3::default())
-        .difference_multi(vec![
-            Tree::sphere(0.6.into(), TreeVec3::default()),
-            Tree::cylinder_z(
-                0.6.into(),
-                2.0.into(),
-                TreeVec3::new(0.0, 0.0, -1.0),
-            ),
-            Tree::cylinder_z(
-                0.6.into(),
-                2.0.into(),
-                TreeVec3::new(0.0, 0.0, -1.0),
-            )
-            .reflect_xz(),
-            Tree::cylinder_z(
-                0.6.into(),
-                2.0.into(),
-                TreeVec3::new(0.0, 0.0, -1.0),
-            )
-            .reflect_yz(),
-        ]);
+fn test_tree_vec_ops() -> Result<()> {
+    let a = TreeVec3::new(1.0, 2.0, 3.0);
+    let b = TreeVec3::new(0.5, -1.0, 4.0);
 
-    f_rep_shape.write_stl(
-        "f-rep-shape.stl",
-        &Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0),
-        // Subdivisions
-        10.0,
-    )?;
+    let sum = a.clone() + b.clone();
+    assert_eq!(sum.x.as_f32()?, 1.5);
+    assert_eq!(sum.y.as_f32()?, 1.0);
+    assert_eq!(sum.z.as_f32()?, 7.0);
+
+    let difference = a.clone() - b;
+    assert_eq!(difference.x.as_f32()?, 0.5);
+    assert_eq!(difference.y.as_f32()?, 3.0);
+    assert_eq!(difference.z.as_f32()?, -1.0);
+
+    let scaled = a * Tree::from(2.0);
+    assert_eq!(scaled.x.as_f32()?, 2.0);
+    assert_eq!(scaled.y.as_f32()?, 4.0);
+    assert_eq!(scaled.z.as_f32()?, 6.0);
+
+    let a = TreeVec2::new(1.0, 2.0);
+    let b = TreeVec2::new(3.0, -4.0);
+    let combined = (a.clone() + b.clone()) * Tree::from(0.5) - a;
+    assert_eq!(combined.x.as_f32()?, 1.0);
+    assert_eq!(combined.y.as_f32()?, -3.0);
+
+    // Components that are not constant.
+    let moved = TreeVec2 {
+        x: Tree::x(),
+        y: Tree::y(),
+    } + b;
+    assert_eq!(moved.x.eval([1.0, 0.0, 0.0]), 4.0);
+    assert_eq!(moved.y.eval([0.0, 1.0, 0.0]), -3.0);
 
     Ok(())
 }
 
-/*
 #[test]
 #[cfg(feature = "stdlib")]
-fn test_eval_3d() -> Result<()> {
-    //let mut variables = Variables::new();
+fn test_tree_vec_clone_debug() {
+    let offset = TreeVec3::new(2.0, 0.0, 0.0);
+    let copy = offset.clone();
+    assert_eq!(copy.x.id(), offset.x.id());
 
-    //let inner_radius = variables.add("inner_radius", 0.6)?;
+    let sphere = || Tree::sphere(0.5, TreeVec3::default());
+    let moved = sphere().moveit(offset);
+    let moved_copy = sphere().moveit(copy);
+    for tree in [&moved, &moved_copy] {
+        assert!(tree.eval([2.0, 0.0, 0.0]) < 0.0);
+        assert!(tree.eval([0.0; 3]) > 0.0);
+    }
 
-    let csg_shape = Tree::sphere(1.0.into(), TreeVec3::default())
-        .difference_multi(vec![
-            Tree::sphere(0.6.into(), TreeVec3::default()),
-            Tree::cylinder_z(
-                0.6.into(),
-                2.0.into(),
-                TreeVec3::new(0.0, 0.0, -1.0),
-            ),
-            Tree::cylinder_z(
-                0.6.into(),
-                2.0.into(),
-                TreeVec3::new(0.0, 0.0, -1.0),
-            )
-            .reflect_xz(),
-            Tree::cylinder_z(
-                0.6.into(),
-                2.0.into(),
-                TreeVec3::new(0.0, 0.0, -1.0),
-            )
-            .reflect_yz(),
-        ]);
+    let debug = format!("{:?}", TreeVec2::new(1.0, 2.0));
+    assert!(debug.starts_with("TreeVec2 { x: Tree { id: "));
+    assert!(debug.contains("y: Tree { id: "));
+}
 
-    //let mut evaluator = Evaluator::new(&csg_shape, &variables);
-    //evaluator.update(&variables);
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_slab() {
+    // Between the planes `x + y = 2` and `x + y = 4`.
+    let normal = TreeVec3::new(1.0, 1.0, 0.0);
+    let slab = Tree::slab(
+        normal.clone(),
+        TreeVec3::new(1.0, 1.0, 0.0),
+        TreeVec3::new(4.0, 0.0, 5.0),
+    );
+    let swapped = Tree::slab(
+        normal,
+        TreeVec3::new(4.0, 0.0, 5.0),
+        TreeVec3::new(1.0, 1.0, 0.0),
+    );
 
-    csg_shape.write_stl(
-        "csg_shape.stl",
-        &Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0),
-        10.0,
-    )?;
-    /*
-    variables.set("inner_radius", 0.4);
-    evaluator.update(&variables);
+    for tree in [&slab, &swapped] {
+        assert!(tree.eval([1.5, 1.5, 0.0]) < 0.0);
+        assert!(tree.eval([3.0, 0.0, -7.0]) < 0.0);
+        // Beyond either plane.
+        assert!(tree.eval([0.5, 0.5, 0.0]) > 0.0);
+        assert!(tree.eval([3.0, 3.0, 0.0]) > 0.0);
+        // The distance to the nearer plane.
+        let distance = 2.0f32.sqrt() / 2.0;
+        assert!((tree.eval([1.5, 1.5, 0.0]) + distance).abs() < 1e-5);
+        assert!((tree.eval([2.5, 2.5, 0.0]) - distance).abs() < 1e-5);
+    }
+}
 
-    csg_shape.write_stl(
-        "csg_shape_0_4.stl",
-        &Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0),
-        10.0,
-    )?;*/
+#[test]
+#[cfg(feature = "stdlib")]
+fn test_reflect_scale_plain_numbers() {
+    let feature = || Tree::sphere(0.25, TreeVec3::new(1.0, 1.0, 1.0));
+
+    let reflected = [
+        feature().reflect_x(0.0),
+        feature().reflect_y(0.0),
+        feature().reflect_z(2.0),
+    ];
+    assert!(reflected[0].eval([-1.0, 1.0, 1.0]) < 0.0);
+    assert!(reflected[1].eval([1.0, -1.0, 1.0]) < 0.0);
+    assert!(reflected[2].eval([1.0, 1.0, 3.0]) < 0.0);
+
+    // Trees are still accepted.
+    let plane = Tree::from(0.0);
+    assert!(feature().reflect_x(plane).eval([-1.0, 1.0, 1.0]) < 0.0);
+
+    let scaled = [
+        feature().scale_x(2.0, 0.0),
+        feature().scale_y(2.0, 0.0),
+        feature().scale_z(0.5, 0.0),
+    ];
+    assert!(scaled[0].eval([2.0, 1.0, 1.0]) < 0.0);
+    assert!(scaled[1].eval([1.0, 2.0, 1.0]) < 0.0);
+    assert!(scaled[2].eval([1.0, 1.0, 0.5]) < 0.0);
+
+    let mirrored = feature().symmetric_x_at(0.5);
+    assert!(mirrored.eval([0.0, 1.0, 1.0]) < 0.0);
+}
+
+#[test]
+fn test_temp_file() -> io::Result<()> {
+    let (a, b) = (TempFile::new("stl")?, TempFile::new("stl")?);
+    assert_ne!(a.path(), b.path());
+    assert!(a.path().is_file());
+
+    let directory = a.path().parent().unwrap().to_path_buf();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&directory)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+
+    drop(a);
+    assert!(!directory.exists());
 
     Ok(())
-}*/
+}
+
+#[test]
+fn test_eval_many() {
+    let sphere = (Tree::x().square() + Tree::y().square() + Tree::z().square())
+        .sqrt()
+        - 1.0.into();
+    // More points than fit in a single batch.
+    let points = (0..1000)
+        .map(|i| [i as f32 * 0.01, 0.5, -0.25])
+        .collect::<Vec<_>>();
+
+    let values = sphere.eval_many(&points);
+
+    assert_eq!(values.len(), points.len());
+    for (value, &point) in values.iter().zip(&points) {
+        assert!((value - sphere.eval(point)).abs() < 1e-6);
+    }
+    assert!(sphere.eval_many(&[]).is_empty());
+}
@@ -0,0 +1,647 @@
+use crate::*;
+use std::{
+    collections::BinaryHeap,
+    io::{self, Write},
+};
+
+/// File format for [`FlatTriangleMesh::write()`].
+///
+/// Variants depend on the enabled features. Hence matches on it need a
+/// wildcard arm.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MeshFormat {
+    /// Binary [`STL`](https://en.wikipedia.org/wiki/STL_(file_format)).
+    /// Triangles do not share vertices.
+    Stl,
+    /// Wavefront [`OBJ`](https://en.wikipedia.org/wiki/Wavefront_.obj_file)
+    /// text with positions and triangles.
+    Obj,
+    /// Binary little endian
+    /// [`PLY`](https://en.wikipedia.org/wiki/PLY_(file_format)) with
+    /// positions and triangles.
+    Ply,
+    /// Binary [`glTF`](https://www.khronos.org/gltf/) (GLB) with positions,
+    /// normals and triangles.
+    #[cfg(feature = "gltf")]
+    Gltf,
+}
+
+impl FlatTriangleMesh {
+    /// Writes the mesh in the given `format` to `writer`.
+    ///
+    /// The mesh is written as it is. Use e.g.
+    /// [`fix_winding()`](FlatTriangleMesh::fix_winding) before, if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::ErrorKind::InvalidInput`] error if the length of
+    /// `positions` or `triangles` is not a multiple of three or if a
+    /// triangle references a vertex that does not exist. Other errors come
+    /// from `writer`.
+    ///
+    /// ```
+    /// # use libfive::*;
+    /// let mesh = FlatTriangleMesh {
+    ///     positions: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+    ///     triangles: vec![0, 1, 2],
+    /// };
+    ///
+    /// let mut obj = Vec::new();
+    /// mesh.write(&mut obj, MeshFormat::Obj)?;
+    ///
+    /// assert!(String::from_utf8(obj).unwrap().contains("f 1 2 3"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn write(
+        &self,
+        writer: impl Write,
+        format: MeshFormat,
+    ) -> io::Result<()> {
+        let mesh = self.to_triangle_mesh::<[f32; 3]>()?;
+
+        match format {
+            MeshFormat::Stl => stl::write_binary_stl(&mesh, writer),
+            MeshFormat::Obj => obj::write_obj(&mesh, writer),
+            MeshFormat::Ply => ply::write_ply(&mesh, writer),
+            #[cfg(feature = "gltf")]
+            MeshFormat::Gltf => glb::write_glb(&mesh, writer),
+        }
+    }
+
+    /// Returns the mesh with grouped coordinates and indices.
+    ///
+    /// Used by [`write()`](FlatTriangleMesh::write) and the conversion into
+    /// a [`TriangleMesh`].
+    pub(crate) fn to_triangle_mesh<T: Point3>(
+        &self,
+    ) -> io::Result<TriangleMesh<T>> {
+        let invalid = |message: &str| {
+            io::Error::new(io::ErrorKind::InvalidInput, message.to_string())
+        };
+
+        if self.positions.len() % 3 != 0 {
+            return Err(invalid("positions length is not a multiple of 3"));
+        }
+        if self.triangles.len() % 3 != 0 {
+            return Err(invalid("triangles length is not a multiple of 3"));
+        }
+        let vertex_count = self.positions.len() / 3;
+        if self.triangles.iter().any(|&v| vertex_count <= v as usize) {
+            return Err(invalid("triangle references a missing vertex"));
+        }
+
+        Ok(TriangleMesh {
+            positions: self
+                .positions
+                .chunks_exact(3)
+                .map(|p| T::new(p[0], p[1], p[2]))
+                .collect(),
+            triangles: self
+                .triangles
+                .chunks_exact(3)
+                .map(|t| [t[0], t[1], t[2]])
+                .collect(),
+        })
+    }
+
+    /// Reduces the mesh to about `target_triangles` triangles.
+    ///
+    /// Edges are collapsed cheapest first, as measured by the quadric error
+    /// metric of [Garland & Heckbert](https://www.cs.cmu.edu/~garland/Papers/quadrics.pdf).
+    /// Each collapse removes the two triangles sharing the edge and moves the
+    /// remaining vertex to where it best preserves the surrounding planes.
+    ///
+    /// To keep a manifold mesh manifold, an edge is not collapsed if
+    ///
+    /// * it is not shared by exactly two triangles, i.e. lies on a boundary
+    ///   or a non-manifold seam,
+    ///
+    /// * its end points have neighbors other than the two opposite vertices
+    ///   in common, which would pinch the surface, or
+    ///
+    /// * the collapse would flip a surrounding triangle.
+    ///
+    /// Rejected edges are reconsidered once a neighboring collapse changed
+    /// them. If no edge can be collapsed anymore the mesh keeps more than
+    /// `target_triangles` triangles. As each collapse removes two triangles
+    /// the result may also have one triangle less than asked for.
+    ///
+    /// Unused positions are removed. The remaining ones are renumbered.
+    pub fn decimate(&mut self, target_triangles: usize) {
+        let mut decimation = Decimation::new(self);
+
+        while decimation.triangle_count > target_triangles {
+            let Some(candidate) = decimation.candidates.pop() else {
+                break;
+            };
+            let [a, b] = candidate.edge;
+            if candidate.stamps != [decimation.stamps[a], decimation.stamps[b]]
+                || !decimation.can_collapse(a, b, candidate.position)
+            {
+                continue;
+            }
+            decimation.collapse(a, b, candidate.position);
+        }
+
+        *self = decimation.into_mesh();
+    }
+
+    /// Flips triangles so all of them are wound counter-clockwise when seen
+    /// from outside the shape.
+    ///
+    /// Triangles are first made consistent with their neighbors across
+    /// shared edges. Each connected part of the mesh is then flipped as a
+    /// whole if it faces the wrong way: outwards, unless it lies inside an
+    /// odd number of other parts, i.e. bounds a cavity.
+    ///
+    /// The mesh is assumed to be manifold and closed. Neighbors are only
+    /// followed across edges shared by exactly two triangles. Parts that are
+    /// only joined by non-manifold edges are hence oriented on their own.
+    /// The orientation of open parts is decided by their signed volume with
+    /// respect to the origin and may be arbitrary.
+    pub fn fix_winding(&mut self) {
+        let positions = unflatten_positions(self);
+        let mut triangles = unflatten_triangles(self);
+
+        // Triangles around each undirected edge.
+        let mut edges = HashMap::<(usize, usize), Vec<usize>>::default();
+        for (index, &triangle) in triangles.iter().enumerate() {
+            for (a, b) in directed_edges(triangle) {
+                edges.entry((a.min(b), a.max(b))).or_default().push(index);
+            }
+        }
+
+        let mut visited = vec![false; triangles.len()];
+        let mut components = Vec::<Vec<usize>>::new();
+        for seed in 0..triangles.len() {
+            if visited[seed] {
+                continue;
+            }
+            visited[seed] = true;
+
+            let mut component = vec![seed];
+            let mut stack = vec![seed];
+            while let Some(triangle) = stack.pop() {
+                for (a, b) in directed_edges(triangles[triangle]) {
+                    let [first, second] = edges[&(a.min(b), a.max(b))][..]
+                    else {
+                        continue;
+                    };
+                    let neighbor =
+                        if first == triangle { second } else { first };
+                    if visited[neighbor] {
+                        continue;
+                    }
+                    visited[neighbor] = true;
+
+                    // Consistently wound neighbors run along the shared edge
+                    // in the opposite direction.
+                    if directed_edges(triangles[neighbor]).contains(&(a, b)) {
+                        triangles[neighbor].swap(1, 2);
+                    }
+                    component.push(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+
+            components.push(component);
+        }
+
+        // Face every part outwards first as winding numbers depend on it.
+        for component in &components {
+            let volume = component
+                .iter()
+                .map(|&triangle| {
+                    let [a, b, c] = triangles[triangle].map(|v| positions[v]);
+                    dot(a, cross(b, c))
+                })
+                .sum::<f64>();
+            if volume < 0.0 {
+                for &triangle in component {
+                    triangles[triangle].swap(1, 2);
+                }
+            }
+        }
+
+        let cavities = components
+            .iter()
+            .enumerate()
+            .map(|(index, component)| {
+                let point = positions[triangles[component[0]][0]];
+                let enclosing = components
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other, _)| other != index)
+                    .filter(|(_, other)| {
+                        0.5 < winding_number(
+                            point,
+                            other.iter().map(|&triangle| {
+                                triangles[triangle].map(|v| positions[v])
+                            }),
+                        )
+                    })
+                    .count();
+                enclosing % 2 == 1
+            })
+            .collect::<Vec<_>>();
+
+        for (component, cavity) in components.iter().zip(cavities) {
+            if cavity {
+                for &triangle in component {
+                    triangles[triangle].swap(1, 2);
+                }
+            }
+        }
+
+        self.triangles = triangles
+            .into_iter()
+            .flatten()
+            .map(|vertex| vertex as u32)
+            .collect();
+    }
+}
+
+/// State of [`FlatTriangleMesh::decimate()`].
+struct Decimation {
+    positions: Vec<[f64; 3]>,
+    triangles: Vec<[usize; 3]>,
+    /// Whether each triangle was collapsed.
+    removed: Vec<bool>,
+    triangle_count: usize,
+    /// Indices of the remaining triangles around each vertex.
+    incident: Vec<Vec<usize>>,
+    quadrics: Vec<Quadric>,
+    /// Bumped when a vertex moves or is removed. Candidates that were
+    /// computed with an older stamp are outdated.
+    stamps: Vec<u32>,
+    candidates: BinaryHeap<Candidate>,
+}
+
+impl Decimation {
+    fn new(mesh: &FlatTriangleMesh) -> Self {
+        let positions = unflatten_positions(mesh);
+        let triangles = unflatten_triangles(mesh);
+
+        let mut incident = vec![Vec::new(); positions.len()];
+        let mut quadrics = vec![Quadric::default(); positions.len()];
+        let mut edges = Vec::with_capacity(3 * triangles.len());
+
+        for (index, &[a, b, c]) in triangles.iter().enumerate() {
+            let normal = normal(positions[a], positions[b], positions[c]);
+            // Weighted by the triangle's area.
+            let quadric = Quadric::plane(normal, positions[a], 0.5);
+
+            for vertex in [a, b, c] {
+                incident[vertex].push(index);
+                quadrics[vertex] = quadrics[vertex] + quadric;
+            }
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                edges.push((from.min(to), from.max(to), index));
+            }
+        }
+
+        edges.sort_unstable();
+        for group in edges.chunk_by(|a, b| (a.0, a.1) == (b.0, b.1)) {
+            // Penalize moving boundary vertices off the plane that is
+            // perpendicular to the triangle through the boundary edge.
+            if let [(a, b, index)] = *group {
+                let [p, q, r] = triangles[index].map(|v| positions[v]);
+                let edge = sub(positions[b], positions[a]);
+                let normal = cross(edge, normal(p, q, r));
+                let quadric = Quadric::plane(normal, positions[a], 1000.0);
+                quadrics[a] = quadrics[a] + quadric;
+                quadrics[b] = quadrics[b] + quadric;
+            }
+        }
+
+        let mut decimation = Self {
+            stamps: vec![0; positions.len()],
+            removed: vec![false; triangles.len()],
+            triangle_count: triangles.len(),
+            positions,
+            triangles,
+            incident,
+            quadrics,
+            candidates: BinaryHeap::with_capacity(edges.len() / 2),
+        };
+
+        edges.dedup_by_key(|&mut (a, b, _)| (a, b));
+        for (a, b, _) in edges {
+            decimation.push_candidate(a, b);
+        }
+
+        decimation
+    }
+
+    /// Queues the collapse of the edge from `a` to `b`.
+    fn push_candidate(&mut self, a: usize, b: usize) {
+        let quadric = self.quadrics[a] + self.quadrics[b];
+        let (pa, pb) = (self.positions[a], self.positions[b]);
+
+        let position = quadric.minimum().unwrap_or_else(|| {
+            let midpoint = [0, 1, 2].map(|i| 0.5 * (pa[i] + pb[i]));
+            [pa, pb, midpoint]
+                .into_iter()
+                .min_by(|p, q| quadric.error(*p).total_cmp(&quadric.error(*q)))
+                .expect("there are three points")
+        });
+
+        self.candidates.push(Candidate {
+            cost: quadric.error(position).max(0.0),
+            edge: [a, b],
+            stamps: [self.stamps[a], self.stamps[b]],
+            position,
+        });
+    }
+
+    /// Returns the vertices sharing a triangle with `vertex`.
+    fn neighbors(&self, vertex: usize) -> Vec<usize> {
+        let mut neighbors = self.incident[vertex]
+            .iter()
+            .flat_map(|&triangle| self.triangles[triangle])
+            .filter(|&v| v != vertex)
+            .collect::<Vec<_>>();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors
+    }
+
+    /// Returns whether merging `b` into `a` at `position` keeps the mesh
+    /// manifold and does not flip any triangle.
+    fn can_collapse(&self, a: usize, b: usize, position: [f64; 3]) -> bool {
+        let shared = self.incident[a]
+            .iter()
+            .filter(|&&triangle| self.triangles[triangle].contains(&b))
+            .count();
+        if shared != 2 {
+            return false;
+        }
+
+        let neighbors = self.neighbors(b);
+        let common = self
+            .neighbors(a)
+            .into_iter()
+            .filter(|v| neighbors.binary_search(v).is_ok())
+            .count();
+        if common != 2 {
+            return false;
+        }
+
+        self.incident[a]
+            .iter()
+            .chain(&self.incident[b])
+            .map(|&triangle| self.triangles[triangle])
+            .filter(|triangle| {
+                !(triangle.contains(&a) && triangle.contains(&b))
+            })
+            .all(|triangle| {
+                let before = triangle.map(|v| self.positions[v]);
+                let after = triangle.map(|v| {
+                    if v == a || v == b {
+                        position
+                    } else {
+                        self.positions[v]
+                    }
+                });
+                let before = normal(before[0], before[1], before[2]);
+                let after = normal(after[0], after[1], after[2]);
+                0.0 < dot(before, after)
+            })
+    }
+
+    /// Merges `b` into `a`, which moves to `position`.
+    fn collapse(&mut self, a: usize, b: usize, position: [f64; 3]) {
+        for triangle in std::mem::take(&mut self.incident[b]) {
+            if self.triangles[triangle].contains(&a) {
+                self.removed[triangle] = true;
+                self.triangle_count -= 1;
+                for vertex in self.triangles[triangle] {
+                    self.incident[vertex].retain(|&t| t != triangle);
+                }
+            } else {
+                for vertex in &mut self.triangles[triangle] {
+                    if *vertex == b {
+                        *vertex = a;
+                    }
+                }
+                self.incident[a].push(triangle);
+            }
+        }
+
+        self.positions[a] = position;
+        self.quadrics[a] = self.quadrics[a] + self.quadrics[b];
+        self.stamps[a] += 1;
+        self.stamps[b] += 1;
+
+        for neighbor in self.neighbors(a) {
+            self.push_candidate(a, neighbor);
+        }
+    }
+
+    /// Returns the remaining triangles and the positions they use.
+    fn into_mesh(self) -> FlatTriangleMesh {
+        let mut indices = vec![u32::MAX; self.positions.len()];
+        let mut mesh = FlatTriangleMesh {
+            positions: Vec::new(),
+            triangles: Vec::with_capacity(3 * self.triangle_count),
+        };
+
+        for (triangle, _) in self
+            .triangles
+            .iter()
+            .zip(&self.removed)
+            .filter(|(_, &removed)| !removed)
+        {
+            for &vertex in triangle {
+                if indices[vertex] == u32::MAX {
+                    indices[vertex] = (mesh.positions.len() / 3) as u32;
+                    mesh.positions
+                        .extend(self.positions[vertex].map(|c| c as f32));
+                }
+                mesh.triangles.push(indices[vertex]);
+            }
+        }
+
+        mesh
+    }
+}
+
+/// A possible edge collapse, ordered by ascending cost.
+struct Candidate {
+    cost: f64,
+    edge: [usize; 2],
+    /// [`Decimation::stamps`] of the edge's vertices when this was queued.
+    stamps: [u32; 2],
+    position: [f64; 3],
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reversed as `BinaryHeap` is a max-heap.
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Symmetric 4×4 matrix measuring the summed squared distance of a point to
+/// a set of planes. Holds the upper triangle, row by row.
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// Returns the quadric of the plane through `point` with the normal
+    /// `normal`, scaled by `weight` times the length of `normal`.
+    fn plane(normal: [f64; 3], point: [f64; 3], weight: f64) -> Self {
+        let length = dot(normal, normal).sqrt();
+        if length == 0.0 {
+            return Self::default();
+        }
+
+        let [a, b, c] = normal.map(|n| n / length);
+        let d = -(a * point[0] + b * point[1] + c * point[2]);
+        let weight = weight * length;
+
+        Self(
+            [
+                a * a,
+                a * b,
+                a * c,
+                a * d,
+                b * b,
+                b * c,
+                b * d,
+                c * c,
+                c * d,
+                d * d,
+            ]
+            .map(|q| q * weight),
+        )
+    }
+
+    fn error(&self, [x, y, z]: [f64; 3]) -> f64 {
+        let q = &self.0;
+        q[0] * x * x
+            + 2.0 * q[1] * x * y
+            + 2.0 * q[2] * x * z
+            + 2.0 * q[3] * x
+            + q[4] * y * y
+            + 2.0 * q[5] * y * z
+            + 2.0 * q[6] * y
+            + q[7] * z * z
+            + 2.0 * q[8] * z
+            + q[9]
+    }
+
+    /// Returns the point with the smallest error, unless the planes do not
+    /// pin down a single point, e.g. on flat or cylindrical parts.
+    fn minimum(&self) -> Option<[f64; 3]> {
+        let q = &self.0;
+        let rows = [[q[0], q[1], q[2]], [q[1], q[4], q[5]], [q[2], q[5], q[7]]];
+        let rhs = [-q[3], -q[6], -q[8]];
+
+        let det = dot(rows[0], cross(rows[1], rows[2]));
+        let scale = q[0] + q[4] + q[7];
+        if det.abs() <= 1e-6 * scale * scale * scale {
+            return None;
+        }
+
+        // Cramer's rule.
+        let columns = |i: usize| {
+            let mut rows = rows;
+            for (row, value) in rows.iter_mut().zip(rhs) {
+                row[i] = value;
+            }
+            dot(rows[0], cross(rows[1], rows[2])) / det
+        };
+
+        Some([columns(0), columns(1), columns(2)])
+    }
+}
+
+impl core::ops::Add for Quadric {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut sum = self.0;
+        for (s, o) in sum.iter_mut().zip(other.0) {
+            *s += o;
+        }
+        Self(sum)
+    }
+}
+
+fn unflatten_positions(mesh: &FlatTriangleMesh) -> Vec<[f64; 3]> {
+    mesh.positions
+        .chunks_exact(3)
+        .map(|p| [p[0] as f64, p[1] as f64, p[2] as f64])
+        .collect()
+}
+
+fn unflatten_triangles(mesh: &FlatTriangleMesh) -> Vec<[usize; 3]> {
+    mesh.triangles
+        .chunks_exact(3)
+        .map(|t| [t[0] as usize, t[1] as usize, t[2] as usize])
+        .collect()
+}
+
+/// Returns the edges of `triangle` in winding order.
+fn directed_edges([a, b, c]: [usize; 3]) -> [(usize, usize); 3] {
+    [(a, b), (b, c), (c, a)]
+}
+
+/// Returns how often the `triangles` wind around `point`, i.e. about one
+/// inside and zero outside of a closed, outwards facing mesh.
+///
+/// Sums the triangles' signed solid angles as seen from `point`, after
+/// [Van Oosterom & Strackee](https://doi.org/10.1109/TBME.1983.325207).
+fn winding_number(
+    point: [f64; 3],
+    triangles: impl Iterator<Item = [[f64; 3]; 3]>,
+) -> f64 {
+    triangles
+        .map(|triangle| {
+            let [a, b, c] = triangle.map(|corner| sub(corner, point));
+            let [la, lb, lc] = [a, b, c].map(|v| dot(v, v).sqrt());
+            let denominator =
+                la * lb * lc + dot(a, b) * lc + dot(a, c) * lb + dot(b, c) * la;
+            2.0 * dot(a, cross(b, c)).atan2(denominator)
+        })
+        .sum::<f64>()
+        / (4.0 * core::f64::consts::PI)
+}
+
+/// Returns the normal of the triangle `a`, `b`, `c`, with a length of twice
+/// its area.
+fn normal(a: [f64; 3], b: [f64; 3], c: [f64; 3]) -> [f64; 3] {
+    cross(sub(b, a), sub(c, a))
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
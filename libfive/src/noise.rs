@@ -0,0 +1,107 @@
+use crate::*;
+
+/// # Noise <a name="noise"></a>
+impl Tree {
+    /// Returns three-dimensional gradient noise, also known as
+    /// [Perlin noise](https://en.wikipedia.org/wiki/Perlin_noise).
+    ///
+    /// The noise has features about `1 / frequency` units apart. It is zero
+    /// at the corners of its lattice, i.e. at integer multiples of
+    /// `1 / frequency`. In between its values lie roughly in `[-1, 1]`.
+    ///
+    /// Each `seed` gives a different, deterministic noise field.
+    ///
+    /// The result is not a distance field. Use it to displace or blend
+    /// shapes, e.g. `shape + Tree::perlin3(4.0, 0) * 0.1.into()`.
+    ///
+    /// `libfive` has no noise primitive and no way to add one through its C
+    /// API. The noise is hence built from arithmetic nodes. Pseudo-random
+    /// gradients are derived per lattice cell by hashing its coordinates
+    /// with `sin()`.
+    pub fn perlin3(frequency: f32, seed: u32) -> Self {
+        let position =
+            [Tree::x(), Tree::y(), Tree::z()].map(|c| c * frequency.into());
+        // `rem()` is never negative so this is `floor()`.
+        let cell = position.clone().map(|c| c.clone() - c.rem(1.0.into()));
+        let local = position.map(|c| c.rem(1.0.into()));
+        let constants = seed_constants(seed);
+
+        // Dot product of the corner's gradient with the offset from the
+        // corner.
+        let corner = |offset: [f32; 3]| {
+            let corner = [0, 1, 2].map(|a| cell[a].clone() + offset[a].into());
+            [0, 1, 2]
+                .into_iter()
+                .map(|a| {
+                    let gradient = hash(&corner, &constants[a])
+                        * Tree::from(2.0)
+                        - Tree::from(1.0);
+                    gradient * (local[a].clone() - offset[a].into())
+                })
+                .reduce(|a, b| a + b)
+                .expect("there are three axes")
+        };
+
+        let weights = local.clone().map(fade);
+        let lerp = |a, b, axis: usize| Tree::mix(a, b, weights[axis].clone());
+
+        lerp(
+            lerp(
+                lerp(corner([0.0, 0.0, 0.0]), corner([1.0, 0.0, 0.0]), 0),
+                lerp(corner([0.0, 1.0, 0.0]), corner([1.0, 1.0, 0.0]), 0),
+                1,
+            ),
+            lerp(
+                lerp(corner([0.0, 0.0, 1.0]), corner([1.0, 0.0, 1.0]), 0),
+                lerp(corner([0.0, 1.0, 1.0]), corner([1.0, 1.0, 1.0]), 0),
+                1,
+            ),
+            2,
+        )
+    }
+}
+
+/// Perlin's quintic fade curve `6t⁵ - 15t⁴ + 10t³`.
+fn fade(t: Tree) -> Tree {
+    t.clone()
+        * t.clone()
+        * t.clone()
+        * (t.clone() * (t * Tree::from(6.0) - Tree::from(15.0))
+            + Tree::from(10.0))
+}
+
+/// Returns a pseudo-random value in `[0, 1)` for the lattice `corner`.
+///
+/// `constants` holds a weight per axis and an offset.
+fn hash(corner: &[Tree; 3], constants: &[f32; 4]) -> Tree {
+    let dot = [0, 1, 2]
+        .into_iter()
+        .map(|a| corner[a].clone() * constants[a].into())
+        .fold(Tree::from(constants[3]), |a, b| a + b);
+
+    (dot.sin() * Tree::from(43758.547)).rem(1.0.into())
+}
+
+/// Derives the constants for [`hash()`] of each gradient component from
+/// `seed`.
+fn seed_constants(seed: u32) -> [[f32; 4]; 3] {
+    // SplitMix64.
+    let mut state = seed as u64;
+    let mut next_unit = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 40) as f32 / (1u64 << 24) as f32
+    };
+
+    [(); 3].map(|_| {
+        [
+            10.0 + 90.0 * next_unit(),
+            10.0 + 90.0 * next_unit(),
+            10.0 + 90.0 * next_unit(),
+            100.0 * next_unit(),
+        ]
+    })
+}
@@ -0,0 +1,30 @@
+use crate::*;
+use std::io::{self, Write};
+
+/// Writes `mesh` as a Wavefront
+/// [`OBJ`](https://en.wikipedia.org/wiki/Wavefront_.obj_file) file to
+/// `writer`.
+///
+/// Only positions (`v`) and triangles (`f`) are written. OBJ indices start
+/// at `1`.
+pub(crate) fn write_obj<T: Point3>(
+    mesh: &TriangleMesh<T>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    writeln!(writer, "# written by libfive-rs")?;
+
+    for position in &mesh.positions {
+        writeln!(
+            writer,
+            "v {} {} {}",
+            position.x(),
+            position.y(),
+            position.z()
+        )?;
+    }
+    for [a, b, c] in &mesh.triangles {
+        writeln!(writer, "f {} {} {}", a + 1, b + 1, c + 1)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use crate::*;
+
+/// Set of [`Variables`] bound to an [`Evaluator`] for parametric models.
+///
+/// Create the model's variables with [`var()`](Parametric::var), build the
+/// model from the returned trees, then [`set()`](Parametric::set) values and
+/// [`mesh()`](Parametric::mesh) the model as often as needed.
+///
+/// The evaluator is created on the first meshing and re-used as long as the
+/// model and the set of variables stay the same. Only the values of the
+/// variables are updated.
+///
+/// ```
+/// # use libfive::*;
+/// let mut parametric = Parametric::new();
+/// let radius = parametric.var("radius", 1.0)?;
+/// let model = (Tree::x().square() + Tree::y().square() + Tree::z().square())
+///     .sqrt()
+///     - radius;
+///
+/// let region = Region3::new(-2.0, 2.0, -2.0, 2.0, -2.0, 2.0);
+/// let small = parametric.mesh::<[f32; 3]>(&model, &region)?;
+///
+/// parametric.set("radius", 1.5)?;
+/// let large = parametric.mesh::<[f32; 3]>(&model, &region)?;
+///
+/// assert!(small.triangles.len() < large.triangles.len());
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Default)]
+pub struct Parametric {
+    variables: Variables,
+    evaluator: Option<(Tree, Evaluator)>,
+}
+
+impl Parametric {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the variable `name` with the `default` value and returns it as a
+    /// tree to build the model from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::VariableAlreadyAdded`] if the variable already
+    /// exists.
+    pub fn var(&mut self, name: &str, default: f32) -> Result<Tree> {
+        let tree = self.variables.add(name, default)?;
+        // The evaluator only knows the variables it was created with.
+        self.evaluator = None;
+
+        Ok(tree)
+    }
+
+    /// Sets the variable `name` to `value`.
+    ///
+    /// The new value is used by the next [`mesh()`](Parametric::mesh).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::VariableNotFound`] if the variable does not exist.
+    pub fn set(&mut self, name: &str, value: f32) -> Result<()> {
+        self.variables.set(name, value)
+    }
+
+    /// Returns the current value of the variable `name` or [`None`] if the
+    /// variable does not exist.
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.variables.get(name)
+    }
+
+    /// Returns the variables.
+    pub fn variables(&self) -> &Variables {
+        &self.variables
+    }
+
+    /// Computes a mesh of `model` in `region` with the current values of the
+    /// variables.
+    ///
+    /// A new [`Evaluator`] is only created if `model` differs from the one
+    /// meshed last or variables were added since.
+    ///
+    /// # Errors
+    ///
+    /// See [`Evaluator::to_triangle_mesh()`].
+    pub fn mesh<T: Point3>(
+        &mut self,
+        model: &Tree,
+        region: &Region3,
+    ) -> Result<TriangleMesh<T>> {
+        match &mut self.evaluator {
+            Some((tree, evaluator)) if tree == model => {
+                evaluator.update(&self.variables)?;
+            }
+            _ => {
+                self.evaluator = Some((
+                    model.clone(),
+                    Evaluator::new(model, &self.variables),
+                ))
+            }
+        }
+
+        self.evaluator
+            .as_ref()
+            .expect("the evaluator was just set")
+            .1
+            .to_triangle_mesh(region)
+    }
+}
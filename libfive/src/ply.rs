@@ -0,0 +1,45 @@
+use crate::*;
+use std::io::{self, Write};
+
+/// Writes `mesh` as a binary little endian
+/// [`PLY`](https://en.wikipedia.org/wiki/PLY_(file_format)) file to
+/// `writer`.
+///
+/// Vertices have `float` `x`, `y` and `z` properties. Faces are lists of
+/// `uint` indices with a `uchar` count.
+pub(crate) fn write_ply<T: Point3>(
+    mesh: &TriangleMesh<T>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    write!(
+        writer,
+        concat!(
+            "ply\n",
+            "format binary_little_endian 1.0\n",
+            "comment written by libfive-rs\n",
+            "element vertex {}\n",
+            "property float x\n",
+            "property float y\n",
+            "property float z\n",
+            "element face {}\n",
+            "property list uchar uint vertex_indices\n",
+            "end_header\n",
+        ),
+        mesh.positions.len(),
+        mesh.triangles.len(),
+    )?;
+
+    for position in &mesh.positions {
+        for value in [position.x(), position.y(), position.z()] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+    for triangle in &mesh.triangles {
+        writer.write_all(&[3])?;
+        for index in triangle {
+            writer.write_all(&index.to_le_bytes())?;
+        }
+    }
+
+    Ok(())
+}
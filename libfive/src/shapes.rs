@@ -1,9 +1,9 @@
 /// Shapes
 impl Tree {
-    pub fn circle(r: TreeFloat, center: TreeVec2) -> Self {
+    pub fn circle(r: impl Into<TreeFloat>, center: TreeVec2) -> Self {
         Self(unsafe {
             sys::circle(
-                r.0,
+                r.into().0,
                 sys::tvec2 {
                     x: center.x.0,
                     y: center.y.0,
@@ -12,11 +12,15 @@ impl Tree {
         })
     }
 
-    pub fn ring(ro: TreeFloat, ri: TreeFloat, center: TreeVec2) -> Self {
+    pub fn ring(
+        ro: impl Into<TreeFloat>,
+        ri: impl Into<TreeFloat>,
+        center: TreeVec2,
+    ) -> Self {
         Self(unsafe {
             sys::ring(
-                ro.0,
-                ri.0,
+                ro.into().0,
+                ri.into().0,
                 sys::tvec2 {
                     x: center.x.0,
                     y: center.y.0,
@@ -25,10 +29,10 @@ impl Tree {
         })
     }
 
-    pub fn polygon(r: TreeFloat, n: u32, center: TreeVec2) -> Self {
+    pub fn polygon(r: impl Into<TreeFloat>, n: u32, center: TreeVec2) -> Self {
         Self(unsafe {
             sys::polygon(
-                r.0,
+                r.into().0,
                 n.try_into().unwrap(),
                 sys::tvec2 {
                     x: center.x.0,
@@ -47,12 +51,16 @@ impl Tree {
         })
     }
 
-    pub fn rounded_rectangle(a: TreeVec2, b: TreeVec2, r: TreeFloat) -> Self {
+    pub fn rounded_rectangle(
+        a: TreeVec2,
+        b: TreeVec2,
+        r: impl Into<TreeFloat>,
+    ) -> Self {
         Self(unsafe {
             sys::rounded_rectangle(
                 sys::tvec2 { x: a.x.0, y: a.y.0 },
                 sys::tvec2 { x: b.x.0, y: b.y.0 },
-                r.0,
+                r.into().0,
             )
         })
     }
@@ -159,7 +167,11 @@ impl Tree {
         })
     }
 
-    pub fn rounded_box(a: TreeVec3, b: TreeVec3, r: TreeFloat) -> Self {
+    pub fn rounded_box(
+        a: TreeVec3,
+        b: TreeVec3,
+        r: impl Into<TreeFloat>,
+    ) -> Self {
         Self(unsafe {
             sys::rounded_box(
                 sys::tvec3 {
@@ -172,15 +184,15 @@ impl Tree {
                     y: b.y.0,
                     z: b.z.0,
                 },
-                r.0,
+                r.into().0,
             )
         })
     }
 
-    pub fn sphere(radius: TreeFloat, center: TreeVec3) -> Self {
+    pub fn sphere(radius: impl Into<TreeFloat>, center: TreeVec3) -> Self {
         Self(unsafe {
             sys::sphere(
-                radius.0,
+                radius.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -207,11 +219,15 @@ impl Tree {
         })
     }
 
-    pub fn cylinder_z(r: TreeFloat, h: TreeFloat, base: TreeVec3) -> Self {
+    pub fn cylinder_z(
+        r: impl Into<TreeFloat>,
+        h: impl Into<TreeFloat>,
+        base: TreeVec3,
+    ) -> Self {
         Self(unsafe {
             sys::cylinder_z(
-                r.0,
-                h.0,
+                r.into().0,
+                h.into().0,
                 sys::tvec3 {
                     x: base.x.0,
                     y: base.y.0,
@@ -222,14 +238,14 @@ impl Tree {
     }
 
     pub fn cone_ang_z(
-        angle: TreeFloat,
-        height: TreeFloat,
+        angle: impl Into<TreeFloat>,
+        height: impl Into<TreeFloat>,
         base: TreeVec3,
     ) -> Self {
         Self(unsafe {
             sys::cone_ang_z(
-                angle.0,
-                height.0,
+                angle.into().0,
+                height.into().0,
                 sys::tvec3 {
                     x: base.x.0,
                     y: base.y.0,
@@ -240,14 +256,14 @@ impl Tree {
     }
 
     pub fn cone_z(
-        radius: TreeFloat,
-        height: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        height: impl Into<TreeFloat>,
         base: TreeVec3,
     ) -> Self {
         Self(unsafe {
             sys::cone_z(
-                radius.0,
-                height.0,
+                radius.into().0,
+                height.into().0,
                 sys::tvec3 {
                     x: base.x.0,
                     y: base.y.0,
@@ -260,24 +276,28 @@ impl Tree {
     pub fn pyramid_z(
         a: TreeVec2,
         b: TreeVec2,
-        zmin: TreeFloat,
-        height: TreeFloat,
+        zmin: impl Into<TreeFloat>,
+        height: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::pyramid_z(
                 sys::tvec2 { x: a.x.0, y: a.y.0 },
                 sys::tvec2 { x: b.x.0, y: b.y.0 },
-                zmin.0,
-                height.0,
+                zmin.into().0,
+                height.into().0,
             )
         })
     }
 
-    pub fn torus_z(ro: TreeFloat, ri: TreeFloat, center: TreeVec3) -> Self {
+    pub fn torus_z(
+        ro: impl Into<TreeFloat>,
+        ri: impl Into<TreeFloat>,
+        center: TreeVec3,
+    ) -> Self {
         Self(unsafe {
             sys::torus_z(
-                ro.0,
-                ri.0,
+                ro.into().0,
+                ri.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -287,7 +307,7 @@ impl Tree {
         })
     }
 
-    pub fn gyroid(period: TreeVec3, thickness: TreeFloat) -> Self {
+    pub fn gyroid(period: TreeVec3, thickness: impl Into<TreeFloat>) -> Self {
         Self(unsafe {
             sys::gyroid(
                 sys::tvec3 {
@@ -295,7 +315,7 @@ impl Tree {
                     y: period.y.0,
                     z: period.z.0,
                 },
-                thickness.0,
+                thickness.into().0,
             )
         })
     }
@@ -304,4 +324,3 @@ impl Tree {
         Self(unsafe { sys::emptiness() })
     }
 }
-
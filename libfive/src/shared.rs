@@ -0,0 +1,138 @@
+use crate::*;
+use std::{ops::Deref, sync::Arc};
+
+/// A [`Tree`] that is referenced from several places in a model.
+///
+/// Created with [`Tree::shared()`]. Dereferences to the [`Tree`] so all
+/// methods taking `&self`, e.g. [`eval()`](Tree::eval) or
+/// [`to_triangle_mesh()`](Tree::to_triangle_mesh), can be called directly.
+///
+/// The arithmetic operators and, with the `stdlib` feature, `union()`,
+/// `intersection()` and `difference()` take a `SharedTree` by reference.
+/// They pass its `libfive` handle on as it is. A new handle is only made
+/// when a consuming method needs an owned [`Tree`], through
+/// [`to_tree()`](SharedTree::to_tree) or [`From`].
+///
+/// The `SharedTree` must be on the left hand side of an operator. Operators
+/// with a [`Tree`] on the left only take another `Tree`. Otherwise the type
+/// of e.g. `tree - 1.0.into()` could no longer be inferred.
+///
+/// # Cost
+///
+/// Neither a `SharedTree` nor [cloning](Tree::clone) a [`Tree`] copies any
+/// nodes. Both share them. But every clone of a `Tree` asks `libfive` for a
+/// new handle, i.e. a heap allocation and an atomic reference count
+/// increment inside `libfive`, and the handle is released again once the
+/// clone was consumed. Cloning a `SharedTree` only increments a counter on
+/// the Rust side and its operators need no clone at all.
+///
+/// The difference is negligible for a handful of uses. A `SharedTree` pays
+/// off for subtrees referenced many times, e.g. in generated models.
+///
+/// ```
+/// # use libfive::*;
+/// let radius = (Tree::x().square() + Tree::y().square()).sqrt().shared();
+/// // A ring between radius 0.5 and 1.
+/// let ring = (&radius - Tree::from(1.0)).max(-(&radius - Tree::from(0.5)));
+///
+/// assert!(ring.eval([0.75, 0.0, 0.0]) < 0.0);
+/// assert!(ring.eval([0.25, 0.0, 0.0]) > 0.0);
+/// ```
+#[derive(Clone)]
+pub struct SharedTree(Arc<Tree>);
+
+impl Tree {
+    /// Turns the tree into a [`SharedTree`] for use in several places.
+    pub fn shared(self) -> SharedTree {
+        SharedTree(Arc::new(self))
+    }
+}
+
+impl SharedTree {
+    /// Returns a new [`Tree`] handle to the shared tree.
+    pub fn to_tree(&self) -> Tree {
+        self.0.as_ref().clone()
+    }
+
+    /// Returns the shared tree, making a new handle if it is still shared.
+    pub fn into_tree(self) -> Tree {
+        Arc::try_unwrap(self.0).unwrap_or_else(|tree| tree.as_ref().clone())
+    }
+}
+
+impl Deref for SharedTree {
+    type Target = Tree;
+
+    fn deref(&self) -> &Tree {
+        &self.0
+    }
+}
+
+impl From<Tree> for SharedTree {
+    fn from(tree: Tree) -> Self {
+        tree.shared()
+    }
+}
+
+impl From<&SharedTree> for Tree {
+    fn from(tree: &SharedTree) -> Self {
+        tree.to_tree()
+    }
+}
+
+/// Applies the binary operation `op` to `a` and `b` without consuming them.
+fn binary(op: Op, a: &Tree, b: &Tree) -> Tree {
+    Tree(unsafe { sys::libfive_tree_binary(op as _, a.0, b.0) })
+}
+
+macro_rules! op_shared {
+    ($func_name:ident, $op_trait:ident, $op_code:ident) => {
+        impl $op_trait<&SharedTree> for &SharedTree {
+            type Output = Tree;
+            #[inline]
+            fn $func_name(self, rhs: &SharedTree) -> Self::Output {
+                binary(Op::$op_code, self, rhs)
+            }
+        }
+
+        impl $op_trait<Tree> for &SharedTree {
+            type Output = Tree;
+            #[inline]
+            fn $func_name(self, rhs: Tree) -> Self::Output {
+                binary(Op::$op_code, self, &rhs)
+            }
+        }
+    };
+}
+
+op_shared!(add, Add, Add);
+op_shared!(div, Div, Div);
+op_shared!(mul, Mul, Mul);
+op_shared!(rem, Rem, Mod);
+op_shared!(sub, Sub, Sub);
+
+impl Neg for &SharedTree {
+    type Output = Tree;
+
+    fn neg(self) -> Self::Output {
+        Tree::neg(self)
+    }
+}
+
+#[cfg(feature = "stdlib")]
+impl SharedTree {
+    /// Returns the union with `b`. See [`Tree::union()`].
+    pub fn union(&self, b: &Tree) -> Tree {
+        Tree(unsafe { sys::_union(self.0 .0, b.0) })
+    }
+
+    /// Returns the intersection with `b`. See [`Tree::intersection()`].
+    pub fn intersection(&self, b: &Tree) -> Tree {
+        Tree(unsafe { sys::intersection(self.0 .0, b.0) })
+    }
+
+    /// Returns the difference with `b`. See [`Tree::difference()`].
+    pub fn difference(&self, b: &Tree) -> Tree {
+        Tree(unsafe { sys::difference(self.0 .0, b.0) })
+    }
+}
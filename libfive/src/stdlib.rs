@@ -1,6 +1,7 @@
 use crate::*;
 
 /// 2D point/vector/normal.
+#[derive(Clone, Debug)]
 pub struct TreeVec2 {
     pub x: Tree,
     pub y: Tree,
@@ -24,7 +25,44 @@ impl Default for TreeVec2 {
     }
 }
 
+impl Add for TreeVec2 {
+    type Output = TreeVec2;
+
+    /// Adds the vectors componentwise.
+    fn add(self, rhs: TreeVec2) -> Self::Output {
+        TreeVec2 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub for TreeVec2 {
+    type Output = TreeVec2;
+
+    /// Subtracts the vectors componentwise.
+    fn sub(self, rhs: TreeVec2) -> Self::Output {
+        TreeVec2 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Mul<TreeFloat> for TreeVec2 {
+    type Output = TreeVec2;
+
+    /// Scales the vector uniformly.
+    fn mul(self, rhs: TreeFloat) -> Self::Output {
+        TreeVec2 {
+            x: self.x * rhs.clone(),
+            y: self.y * rhs,
+        }
+    }
+}
+
 /// 3D point/vector/normal.
+#[derive(Clone, Debug)]
 pub struct TreeVec3 {
     pub x: Tree,
     pub y: Tree,
@@ -39,6 +77,39 @@ impl TreeVec3 {
             z: Tree::from(z),
         }
     }
+
+    /// Returns the dot product of `self` and `other`.
+    pub fn dot(&self, other: &TreeVec3) -> Tree {
+        self.x.clone() * other.x.clone()
+            + self.y.clone() * other.y.clone()
+            + self.z.clone() * other.z.clone()
+    }
+
+    /// Returns the cross product of `self` and `other`.
+    pub fn cross(&self, other: &TreeVec3) -> TreeVec3 {
+        TreeVec3 {
+            x: self.y.clone() * other.z.clone()
+                - self.z.clone() * other.y.clone(),
+            y: self.z.clone() * other.x.clone()
+                - self.x.clone() * other.z.clone(),
+            z: self.x.clone() * other.y.clone()
+                - self.y.clone() * other.x.clone(),
+        }
+    }
+
+    /// Returns the vector scaled to unit length.
+    ///
+    /// Where the vector has zero length its components are divided by zero
+    /// and evaluate to NaN. Use [`nan_fill()`](Tree::nan_fill) on them to
+    /// substitute a fallback.
+    pub fn normalized(&self) -> TreeVec3 {
+        let length = Tree::length3(self);
+        TreeVec3 {
+            x: self.x.clone() / length.clone(),
+            y: self.y.clone() / length.clone(),
+            z: self.z.clone() / length,
+        }
+    }
 }
 
 impl Default for TreeVec3 {
@@ -51,48 +122,1316 @@ impl Default for TreeVec3 {
     }
 }
 
+impl Add for TreeVec3 {
+    type Output = TreeVec3;
+
+    /// Adds the vectors componentwise.
+    fn add(self, rhs: TreeVec3) -> Self::Output {
+        TreeVec3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl Sub for TreeVec3 {
+    type Output = TreeVec3;
+
+    /// Subtracts the vectors componentwise.
+    fn sub(self, rhs: TreeVec3) -> Self::Output {
+        TreeVec3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl Mul<TreeFloat> for TreeVec3 {
+    type Output = TreeVec3;
+
+    /// Scales the vector uniformly.
+    fn mul(self, rhs: TreeFloat) -> Self::Output {
+        TreeVec3 {
+            x: self.x * rhs.clone(),
+            y: self.y * rhs.clone(),
+            z: self.z * rhs,
+        }
+    }
+}
+
 include!("shapes.rs");
 include!("generators.rs");
 include!("csg.rs");
 
+impl Default for Tree {
+    /// Returns [`Tree::emptiness()`], the empty set.
+    ///
+    /// This is the identity of [`union()`](Tree::union).
+    fn default() -> Self {
+        Tree::emptiness()
+    }
+}
+
+impl Tree {
+    /// Returns all of space, the counterpart of
+    /// [`emptiness()`](Tree::emptiness).
+    ///
+    /// Its value is negative infinity everywhere. This is the identity of
+    /// [`intersection()`](Tree::intersection).
+    pub fn fullness() -> Self {
+        f32::NEG_INFINITY.into()
+    }
+}
+
 /// A collection of [`Tree`]s.
 ///
-/// This is used for the [`*_multi()`](Tree#multi_csg) CSG operations.
+/// The [`*_multi()`](Tree#multi_csg) CSG operations accept this or any
+/// other iterator over [`Tree`]s.
 pub type Trees = Vec<Tree>;
 
 /// <a name="multi_csg"></a>
 /// Operations taking multiple 2nd arguments.
 impl Tree {
-    pub fn union_multi(self, trees: Trees) -> Self {
-        if trees.is_empty() {
-            Tree::emptiness()
-        } else {
-            trees.into_iter().fold(self, |a, b| a.union(b))
-        }
+    /// Returns the union of `self` and all `trees`.
+    ///
+    /// Returns `self` if there are no `trees`.
+    pub fn union_multi(self, trees: impl IntoIterator<Item = Tree>) -> Self {
+        trees.into_iter().fold(self, |a, b| a.union(b))
+    }
+
+    /// Returns the intersection of `self` and all `trees`.
+    ///
+    /// Returns `self` if there are no `trees`.
+    pub fn intersection_multi(
+        self,
+        trees: impl IntoIterator<Item = Tree>,
+    ) -> Self {
+        trees.into_iter().fold(self, |a, b| a.intersection(b))
     }
 
-    pub fn intersection_multi(self, trees: Trees) -> Self {
-        if trees.is_empty() {
-            Tree::emptiness()
-        } else {
-            trees.into_iter().fold(self, |a, b| a.intersection(b))
+    /// Returns `self` with all `trees` subtracted from it.
+    ///
+    /// ```
+    /// # use libfive::*;
+    /// let cylinder =
+    ///     || Tree::cylinder_z(0.6, 2.0, TreeVec3::new(0.0, 0.0, -1.0));
+    ///
+    /// let f_rep_shape =
+    ///     Tree::sphere(1.0, TreeVec3::default()).difference_multi(vec![
+    ///         Tree::sphere(0.6, TreeVec3::default()),
+    ///         cylinder(),
+    ///         cylinder().reflect_xz(),
+    ///         cylinder().reflect_yz(),
+    ///     ]);
+    ///
+    /// assert!(f_rep_shape.eval([0.5, 0.5, 0.5]) < 0.0);
+    /// assert!(f_rep_shape.eval([0.0, 0.0, 0.0]) > 0.0);
+    /// assert!(f_rep_shape.eval([0.0, 0.0, 0.8]) > 0.0);
+    /// ```
+    pub fn difference_multi(
+        self,
+        trees: impl IntoIterator<Item = Tree>,
+    ) -> Self {
+        match trees.into_iter().reduce(|a, b| a.union(b)) {
+            Some(union) => self.intersection(union.inverse()),
+            None => self,
         }
     }
 
-    pub fn difference_multi(self, trees: Trees) -> Self {
-        if trees.is_empty() {
-            self
-        } else {
-            self.intersection(
-                trees
-                    .into_iter()
-                    .reduce(|a, b| a.union(b))
-                    .expect("there is at least one tree")
-                    .inverse(),
+    /// Returns the union of all `trees`.
+    ///
+    /// Returns [`emptiness()`](Tree::emptiness) if there are no `trees`.
+    pub fn union_all(trees: impl IntoIterator<Item = Tree>) -> Self {
+        trees
+            .into_iter()
+            .reduce(|a, b| a.union(b))
+            .unwrap_or_else(Tree::emptiness)
+    }
+
+    /// Returns the intersection of all `trees`.
+    ///
+    /// Returns [`fullness()`](Tree::fullness) if there are no `trees`.
+    pub fn intersection_all(trees: impl IntoIterator<Item = Tree>) -> Self {
+        trees
+            .into_iter()
+            .reduce(|a, b| a.intersection(b))
+            .unwrap_or_else(Tree::fullness)
+    }
+}
+
+/// Builds a CSG tree from nested `union`, `intersection` and `difference`
+/// blocks.
+///
+/// Each block takes a comma-separated list of operands. An operand is
+/// either another block or any expression evaluating to a [`Tree`]. A
+/// `difference` subtracts all operands after the first one from it.
+///
+/// Blocks expand to the [`*_multi()`](Tree#multi_csg) operations. A block
+/// with a single operand is that operand. The result is the same tree the
+/// equivalent method chain builds.
+///
+/// ```
+/// # use libfive::*;
+/// let shape = csg! {
+///     difference {
+///         intersection {
+///             Tree::box_exact_centered(
+///                 TreeVec3::new(2.0, 2.0, 2.0),
+///                 TreeVec3::default(),
+///             ),
+///             Tree::sphere(1.3, TreeVec3::default()),
+///         },
+///         union {
+///             Tree::cylinder_z(
+///                 0.5,
+///                 4.0,
+///                 TreeVec3::new(0.0, 0.0, -2.0),
+///             ),
+///             Tree::cylinder_z(
+///                 0.5,
+///                 4.0,
+///                 TreeVec3::new(0.0, 0.0, -2.0),
+///             )
+///             .reflect_xz(),
+///         },
+///     }
+/// };
+///
+/// assert!(shape.eval([0.0; 3]) > 0.0);
+/// assert!(shape.eval([0.8, 0.8, 0.0]) < 0.0);
+/// ```
+#[macro_export]
+macro_rules! csg {
+    (union { $($operands:tt)* }) => {
+        $crate::csg!(@apply union_multi [] $($operands)*)
+    };
+    (intersection { $($operands:tt)* }) => {
+        $crate::csg!(@apply intersection_multi [] $($operands)*)
+    };
+    (difference { $($operands:tt)* }) => {
+        $crate::csg!(@apply difference_multi [] $($operands)*)
+    };
+    // All operands are expressions now.
+    (@apply $method:ident [$only:expr]) => {
+        $only
+    };
+    (@apply $method:ident [$first:expr $(, $rest:expr)+]) => {
+        $first.$method([$($rest),+])
+    };
+    // Splits off the next operand, expanding nested blocks.
+    (
+        @apply $method:ident [$($done:expr),*]
+        $block:ident { $($inner:tt)* } $(, $($tail:tt)*)?
+    ) => {
+        $crate::csg!(
+            @apply $method
+            [$($done,)* $crate::csg!($block { $($inner)* })]
+            $($($tail)*)?
+        )
+    };
+    (
+        @apply $method:ident [$($done:expr),*]
+        $operand:expr $(, $($tail:tt)*)?
+    ) => {
+        $crate::csg!(@apply $method [$($done,)* $operand] $($($tail)*)?)
+    };
+    ($tree:expr) => {
+        $tree
+    };
+}
+
+/// Euclidean CSG.
+///
+/// [`union()`](Tree::union) and [`intersection()`](Tree::intersection) are
+/// the `min()` resp. `max()` of their operands. For signed distance fields
+/// the union is exact outside the shape but underestimates the distance
+/// inside, near where the operands overlap. Likewise the intersection
+/// underestimates it outside, near edges. [`offset()`](Tree::offset) and
+/// [`shell()`](Tree::shell) then produce flat bevels instead of rounded
+/// ones there.
+///
+/// These variants replace the underestimated part with the Euclidean
+/// length of both distances. The result is only exact where the surfaces
+/// meet at right angles. Elsewhere it can overestimate the distance, by up
+/// to a factor of √2 where the surfaces coincide, e.g. `-√2` instead of
+/// `-1` at the center of the union of a unit sphere with itself. Unlike the
+/// cheap versions the result then is no bound of the distance anymore.
+/// Sphere tracing may step through the surface and offsets may cut into
+/// the shape there.
+///
+/// They cost a square root and a few more operations per evaluation. The
+/// shapes, i.e. the zero sets, are the same as those of the cheap versions.
+impl Tree {
+    /// Returns the union with `b` with corrected distances inside.
+    pub fn union_euclidean(self, b: Tree) -> Self {
+        let zero = || Tree::from(0.0);
+        let inside = Tree::length2(&TreeVec2 {
+            x: self.clone().min(zero()),
+            y: b.clone().min(zero()),
+        });
+
+        self.min(b).max(zero()) - inside
+    }
+
+    /// Returns the intersection with `b` with corrected distances outside.
+    pub fn intersection_euclidean(self, b: Tree) -> Self {
+        let zero = || Tree::from(0.0);
+        let outside = Tree::length2(&TreeVec2 {
+            x: self.clone().max(zero()),
+            y: b.clone().max(zero()),
+        });
+
+        self.max(b).min(zero()) + outside
+    }
+}
+
+/// Vector helpers.
+impl Tree {
+    /// Returns the Euclidean length of `v`.
+    pub fn length2(v: &TreeVec2) -> Self {
+        (v.x.square() + v.y.square()).sqrt()
+    }
+
+    /// Returns the Euclidean length of `v`.
+    pub fn length3(v: &TreeVec3) -> Self {
+        (v.x.square() + v.y.square() + v.z.square()).sqrt()
+    }
+
+    /// Returns the Euclidean distance between `a` and `b`.
+    pub fn distance3(a: &TreeVec3, b: &TreeVec3) -> Self {
+        ((a.x.clone() - b.x.clone()).square()
+            + (a.y.clone() - b.y.clone()).square()
+            + (a.z.clone() - b.z.clone()).square())
+        .sqrt()
+    }
+}
+
+/// Boxes.
+impl Tree {
+    /// Returns a box of `size`, centered at `center`, whose edges and
+    /// corners are rounded with the radius `r`.
+    ///
+    /// Unlike [`rounded_box()`](Tree::rounded_box) this takes an absolute
+    /// radius. `r` must not exceed half the smallest extent of the box. The
+    /// result is an exact distance field.
+    pub fn rounded_box_centered(
+        size: TreeVec3,
+        center: TreeVec3,
+        r: impl Into<TreeFloat>,
+    ) -> Self {
+        let r = r.into();
+        let [x, y, z] =
+            face_distances(size, center).map(|distance| distance + r.clone());
+
+        let outside = Tree::length3(&TreeVec3 {
+            x: x.max(0.0.into()),
+            y: y.max(0.0.into()),
+            z: z.max(0.0.into()),
+        });
+        let inside = x.max(y).max(z).min(0.0.into());
+
+        outside + inside - r
+    }
+
+    /// Returns a box of `size`, centered at `center`, whose edges are cut
+    /// by flat 45° bevels.
+    ///
+    /// Each bevel cuts `chamfer` off both faces meeting at its edge. The
+    /// corners are cut by all three bevels meeting there.
+    pub fn chamfered_box(
+        size: TreeVec3,
+        center: TreeVec3,
+        chamfer: impl Into<TreeFloat>,
+    ) -> Self {
+        let chamfer = chamfer.into();
+        let [x, y, z] = face_distances(size.clone(), center.clone());
+        let bevel = |a: &Tree, b: &Tree| {
+            (a.clone() + b.clone() + chamfer.clone())
+                * core::f32::consts::FRAC_1_SQRT_2.into()
+        };
+
+        Tree::max_all([
+            bevel(&x, &y),
+            bevel(&y, &z),
+            bevel(&x, &z),
+            Tree::box_exact_centered(size, center),
+        ])
+    }
+}
+
+/// Returns the signed distance from the pair of faces of the box of `size`,
+/// centered at `center`, along each axis. Negative between the faces.
+fn face_distances(size: TreeVec3, center: TreeVec3) -> [Tree; 3] {
+    [
+        (Tree::x() - center.x).abs() - size.x * 0.5.into(),
+        (Tree::y() - center.y).abs() - size.y * 0.5.into(),
+        (Tree::z() - center.z).abs() - size.z * 0.5.into(),
+    ]
+}
+
+/// Planes.
+impl Tree {
+    /// Returns the slab between the two planes perpendicular to `normal`
+    /// through `point_a` and `point_b`.
+    ///
+    /// This is the intersection of two opposing
+    /// [`half_space()`](Tree::half_space)s. The points may be given in
+    /// either order and `normal` need not be normalized.
+    ///
+    /// Like for all shapes the field is negative between the planes,
+    /// i.e. inside, and positive beyond either of them. It is the exact
+    /// distance to the nearer plane.
+    pub fn slab(
+        normal: TreeVec3,
+        point_a: TreeVec3,
+        point_b: TreeVec3,
+    ) -> Self {
+        let length = Tree::length3(&normal);
+        // Offsets along the normalized normal.
+        let offset = |point: &TreeVec3| point.dot(&normal) / length.clone();
+        let (a, b) = (offset(&point_a), offset(&point_b));
+        let position = offset(&TreeVec3 {
+            x: Tree::x(),
+            y: Tree::y(),
+            z: Tree::z(),
+        });
+
+        let center = (a.clone() + b.clone()) * Tree::from(0.5);
+        let half_thickness = ((a - b) * Tree::from(0.5)).abs();
+        (position - center).abs() - half_thickness
+    }
+}
+
+/// Polygons.
+///
+/// Both are exact distance fields. They are built for a single wedge of
+/// `2π / n` and repeated with [`symmetric_polar_z()`](Tree::symmetric_polar_z).
+/// One corner points along the positive `x` axis.
+impl Tree {
+    /// Returns a regular polygon with `n` sides and the circumradius `r`,
+    /// whose corners are rounded with `corner_radius`.
+    ///
+    /// This is the sharp polygon shrunk by `corner_radius` and grown back
+    /// by the same amount. Hence rounding moves the corners inwards but
+    /// leaves the sides in place. `n` must be at least `3`.
+    pub fn rounded_polygon(
+        r: impl Into<TreeFloat>,
+        n: u32,
+        corner_radius: impl Into<TreeFloat>,
+        center: TreeVec2,
+    ) -> Self {
+        let r = r.into();
+        let corner_radius = corner_radius.into();
+        let half_angle = std::f32::consts::PI / n.max(1) as f32;
+        let (x, y) = wedge_coordinates(n, &center);
+
+        // The shrunk polygon's apothem & half the length of its sides.
+        let apothem = r * half_angle.cos().into() - corner_radius.clone();
+        let half_side = apothem.clone() * half_angle.tan().into();
+
+        let x = x - apothem;
+        let outside = Tree::length2(&TreeVec2 {
+            x: x.clone().max(0.0.into()),
+            y: (y - half_side).max(0.0.into()),
+        });
+
+        (outside + x.min(0.0.into()) - corner_radius)
+            .symmetric_polar_z(n, center)
+    }
+
+    /// Returns a star with `n` points at the radius `outer_r` and `n` inner
+    /// corners in between at the radius `inner_r`.
+    pub fn star(
+        outer_r: impl Into<TreeFloat>,
+        inner_r: impl Into<TreeFloat>,
+        n: u32,
+        center: TreeVec2,
+    ) -> Self {
+        let outer_r = outer_r.into();
+        let inner_r = inner_r.into();
+        let half_angle = std::f32::consts::PI / n.max(1) as f32;
+        let (x, y) = wedge_coordinates(n, &center);
+
+        // The edge from the inner corner to the point.
+        let start = [inner_r.clone(), 0.0.into()];
+        let end = [
+            outer_r.clone() * half_angle.cos().into(),
+            outer_r * half_angle.sin().into(),
+        ];
+        let edge = [
+            end[0].clone() - start[0].clone(),
+            end[1].clone() - start[1].clone(),
+        ];
+        let offset = [x - start[0].clone(), y - start[1].clone()];
+
+        let t = ((offset[0].clone() * edge[0].clone()
+            + offset[1].clone() * edge[1].clone())
+            / (edge[0].square() + edge[1].square()))
+        .clamp(0.0, 1.0);
+        let distance = Tree::length2(&TreeVec2 {
+            x: offset[0].clone() - t.clone() * edge[0].clone(),
+            y: offset[1].clone() - t * edge[1].clone(),
+        });
+        // Positive outside, i.e. on the far side of the edge.
+        let side = offset[0].clone() * edge[1].clone()
+            - offset[1].clone() * edge[0].clone();
+
+        distance.copysign(side).symmetric_polar_z(n, center)
+    }
+}
+
+/// Returns the coordinates relative to `center`, turned so that the middle
+/// of the first wedge of `2π / n` is along `x` and folded across it, i.e.
+/// with `y` made non-negative.
+fn wedge_coordinates(n: u32, center: &TreeVec2) -> (Tree, Tree) {
+    let (sin, cos) = (std::f32::consts::PI / n.max(1) as f32).sin_cos();
+    let dx = Tree::x() - center.x.clone();
+    let dy = Tree::y() - center.y.clone();
+
+    (
+        dx.clone() * cos.into() + dy.clone() * sin.into(),
+        (dy * cos.into() - dx * sin.into()).abs(),
+    )
+}
+
+/// Repetition.
+impl Tree {
+    /// Places `n` copies of `shape`, the `i`-th one moved by `i * delta`.
+    ///
+    /// Like [`array_x()`](Tree::array_x) but along an arbitrary direction.
+    pub fn array_along(shape: Tree, n: u32, delta: TreeVec3) -> Self {
+        Tree::union_all((0..n).map(|i| {
+            let i = Tree::from(i as f32);
+            shape.clone().moveit(TreeVec3 {
+                x: i.clone() * delta.x.clone(),
+                y: i.clone() * delta.y.clone(),
+                z: i * delta.z.clone(),
+            })
+        }))
+    }
+
+    /// Lays `trees` out in a grid with `cols` columns and unions them.
+    ///
+    /// The `i`-th tree is moved to column `i % cols` and row `i / cols`,
+    /// i.e. by `spacing.x` per column along `x` and `spacing.y` per row
+    /// along `y`. The last row may be partially filled. A `cols` of `0` is
+    /// treated as `1`.
+    ///
+    /// This is handy to preview or mesh a sweep of variations of a model
+    /// at once. `spacing` should exceed the size of the trees to keep them
+    /// apart.
+    pub fn grid_layout(
+        trees: Vec<Tree>,
+        cols: usize,
+        spacing: TreeVec2,
+    ) -> Self {
+        let cols = cols.max(1);
+
+        Tree::union_all(trees.into_iter().enumerate().map(|(index, tree)| {
+            let column = Tree::from((index % cols) as f32);
+            let row = Tree::from((index / cols) as f32);
+            tree.moveit(TreeVec3 {
+                x: column * spacing.x.clone(),
+                y: row * spacing.y.clone(),
+                z: 0.0.into(),
+            })
+        }))
+    }
+
+    /// Repeats the shape infinitely along all axes.
+    ///
+    /// Space is divided into cells of size `period`, centered on multiples
+    /// of `period`. Each cell contains a copy of the part of the shape
+    /// inside the cell around the origin.
+    pub fn repeat_xyz(self, period: TreeVec3) -> Self {
+        let wrap = |coordinate: Tree, period: Tree| {
+            let half = period.clone() * Tree::from(0.5);
+            (coordinate + half.clone()).rem(period) - half
+        };
+
+        self.remap(
+            wrap(Tree::x(), period.x),
+            wrap(Tree::y(), period.y),
+            wrap(Tree::z(), period.z),
+        )
+    }
+
+    /// Repeats the shape `count` times along each axis.
+    ///
+    /// Like [`repeat_xyz()`](Tree::repeat_xyz) but copies are only placed at
+    /// `i * period` for `i` in `0..count`. A `count` of `0` is treated as
+    /// `1`.
+    pub fn repeat_limited(self, period: TreeVec3, count: [u32; 3]) -> Self {
+        let wrap = |coordinate: Tree, period: Tree, count: u32| {
+            let cell = coordinate.clone() / period.clone() + Tree::from(0.5);
+            // `rem()` is never negative so this is `floor()`.
+            let cell = (cell.clone() - cell.rem(1.0.into()))
+                .clamp(0.0, (count.max(1) - 1) as f32);
+            coordinate - period * cell
+        };
+
+        self.remap(
+            wrap(Tree::x(), period.x, count[0]),
+            wrap(Tree::y(), period.y, count[1]),
+            wrap(Tree::z(), period.z, count[2]),
+        )
+    }
+
+    /// Gives the shape `n`-fold rotational symmetry around the axis parallel
+    /// to `z` through `center`.
+    ///
+    /// The angle around the axis is folded into a single wedge of `2π / n`,
+    /// starting at the positive `x` direction. Unlike
+    /// [`array_polar_z()`](Tree::array_polar_z), which unions rotated
+    /// copies, every wedge is the same part of the field. Features crossing
+    /// the wedge boundaries therefore interlock seamlessly.
+    pub fn symmetric_polar_z(self, n: u32, center: TreeVec2) -> Self {
+        let wedge = std::f32::consts::TAU / n.max(1) as f32;
+
+        let dx = Tree::x() - center.x.clone();
+        let dy = Tree::y() - center.y.clone();
+        let radius = Tree::length2(&TreeVec2 {
+            x: dx.clone(),
+            y: dy.clone(),
+        });
+        let angle = dy.atan2(dx).rem(wedge.into());
+
+        self.remap(
+            center.x + radius.clone() * angle.cos(),
+            center.y + radius * angle.sin(),
+            Tree::z(),
+        )
+    }
+
+    /// Places `n` copies of `shape` evenly on a circle of `radius` around
+    /// the axis parallel to `z` through `center`.
+    ///
+    /// The origin of `shape` is moved to the point at `start_angle` on the
+    /// circle, measured counterclockwise from the positive `x` direction.
+    /// Then [`array_polar_z()`](Tree::array_polar_z) adds the copies rotated
+    /// by multiples of `2π / n` around `center`. With a `radius` of `0` this
+    /// is `array_polar_z()` with `shape` moved to `center`.
+    pub fn array_polar_z_offset(
+        shape: Tree,
+        n: u32,
+        radius: impl Into<TreeFloat>,
+        start_angle: impl Into<TreeFloat>,
+        center: TreeVec2,
+    ) -> Self {
+        let radius = radius.into();
+        let start_angle = start_angle.into();
+        let offset = TreeVec3 {
+            x: center.x.clone() + radius.clone() * start_angle.cos(),
+            y: center.y.clone() + radius * start_angle.sin(),
+            z: 0.0.into(),
+        };
+
+        Tree::array_polar_z(shape.moveit(offset), n, center)
+    }
+}
+
+/// Revolution around further axes.
+///
+/// Like [`revolve_y()`](Tree::revolve_y) these revolve a 2D shape in the
+/// `xy` plane. They swap coordinates before and after revolving around `y`.
+impl Tree {
+    /// Revolves the 2D shape around the line `y = y0` parallel to the `x`
+    /// axis.
+    pub fn revolve_x(self, y0: impl Into<TreeFloat>) -> Self {
+        self.remap(Tree::y(), Tree::x(), Tree::z())
+            .revolve_y(y0)
+            .remap(Tree::y(), Tree::x(), Tree::z())
+    }
+
+    /// Revolves the 2D shape around the line `x = x0` parallel to the `z`
+    /// axis.
+    ///
+    /// The shape's `y` coordinate becomes the `z` coordinate. I.e. this is
+    /// [`revolve_y()`](Tree::revolve_y) with the result turned so that its
+    /// axis points along `z`.
+    pub fn revolve_z(self, x0: impl Into<TreeFloat>) -> Self {
+        self.revolve_y(x0).remap(Tree::x(), Tree::z(), Tree::y())
+    }
+}
+
+/// Symmetry about offset planes.
+///
+/// Like [`symmetric_x()`](Tree::symmetric_x),
+/// [`symmetric_y()`](Tree::symmetric_y) and
+/// [`symmetric_z()`](Tree::symmetric_z) but about a plane through the given
+/// coordinate instead of the origin. The part of the shape on the positive
+/// side of the plane is kept and mirrored onto the negative side. With a
+/// coordinate of `0` they equal the existing functions.
+///
+/// Like [`reflect_x()`](Tree::reflect_x) they take the coordinate as
+/// anything that converts into a [`TreeFloat`], e.g. a plain `2.0`.
+impl Tree {
+    /// Makes the shape symmetric about the plane `x = x0`.
+    pub fn symmetric_x_at(self, x0: impl Into<TreeFloat>) -> Self {
+        let x0 = x0.into();
+        self.remap(x0.clone() + (Tree::x() - x0).abs(), Tree::y(), Tree::z())
+    }
+
+    /// Makes the shape symmetric about the plane `y = y0`.
+    pub fn symmetric_y_at(self, y0: impl Into<TreeFloat>) -> Self {
+        let y0 = y0.into();
+        self.remap(Tree::x(), y0.clone() + (Tree::y() - y0).abs(), Tree::z())
+    }
+
+    /// Makes the shape symmetric about the plane `z = z0`.
+    pub fn symmetric_z_at(self, z0: impl Into<TreeFloat>) -> Self {
+        let z0 = z0.into();
+        self.remap(Tree::x(), Tree::y(), z0.clone() + (Tree::z() - z0).abs())
+    }
+}
+
+/// Tapering & shearing along further axes.
+///
+/// Like [`taper_x_y()`](Tree::taper_x_y),
+/// [`taper_xy_z()`](Tree::taper_xy_z) and [`shear_x_y()`](Tree::shear_x_y)
+/// but for other pairs of axes. In `taper_a_b()` and `shear_a_b()` the `a`
+/// coordinate is scaled resp. shifted as a function of the `b` coordinate.
+///
+/// They permute coordinates before and after calling the existing
+/// operations. Hence `base` holds coordinates along the actual axes. The
+/// `base` of the 3D variants is a point. Its coordinate along the axis not
+/// involved is ignored.
+impl Tree {
+    /// Scales `y` around `base.y`, from `base_scale` at `x = base.x` to
+    /// `scale` at `x = base.x + h`.
+    pub fn taper_y_x(
+        self,
+        base: TreeVec2,
+        h: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::y(), Tree::x(), Tree::z())
+            .taper_x_y(
+                TreeVec2 {
+                    x: base.y,
+                    y: base.x,
+                },
+                h,
+                scale,
+                base_scale,
             )
-        }
+            .remap(Tree::y(), Tree::x(), Tree::z())
+    }
+
+    /// Scales `x` around `base.x`, from `base_scale` at `z = base.z` to
+    /// `scale` at `z = base.z + h`.
+    pub fn taper_x_z(
+        self,
+        base: TreeVec3,
+        h: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::x(), Tree::z(), Tree::y())
+            .taper_x_y(
+                TreeVec2 {
+                    x: base.x,
+                    y: base.z,
+                },
+                h,
+                scale,
+                base_scale,
+            )
+            .remap(Tree::x(), Tree::z(), Tree::y())
+    }
+
+    /// Scales `z` around `base.z`, from `base_scale` at `x = base.x` to
+    /// `scale` at `x = base.x + h`.
+    pub fn taper_z_x(
+        self,
+        base: TreeVec3,
+        h: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::y(), Tree::z(), Tree::x())
+            .taper_x_y(
+                TreeVec2 {
+                    x: base.z,
+                    y: base.x,
+                },
+                h,
+                scale,
+                base_scale,
+            )
+            .remap(Tree::z(), Tree::x(), Tree::y())
+    }
+
+    /// Scales `y` around `base.y`, from `base_scale` at `z = base.z` to
+    /// `scale` at `z = base.z + h`.
+    pub fn taper_y_z(
+        self,
+        base: TreeVec3,
+        h: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::z(), Tree::x(), Tree::y())
+            .taper_x_y(
+                TreeVec2 {
+                    x: base.y,
+                    y: base.z,
+                },
+                h,
+                scale,
+                base_scale,
+            )
+            .remap(Tree::y(), Tree::z(), Tree::x())
+    }
+
+    /// Scales `z` around `base.z`, from `base_scale` at `y = base.y` to
+    /// `scale` at `y = base.y + h`.
+    pub fn taper_z_y(
+        self,
+        base: TreeVec3,
+        h: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::z(), Tree::y(), Tree::x())
+            .taper_x_y(
+                TreeVec2 {
+                    x: base.z,
+                    y: base.y,
+                },
+                h,
+                scale,
+                base_scale,
+            )
+            .remap(Tree::z(), Tree::y(), Tree::x())
+    }
+
+    /// Scales `y` and `z` around `base`, from `base_scale` at `x = base.x`
+    /// to `scale` at `x = base.x + height`.
+    pub fn taper_yz_x(
+        self,
+        base: TreeVec3,
+        height: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::z(), Tree::y(), Tree::x())
+            .taper_xy_z(
+                TreeVec3 {
+                    x: base.z,
+                    y: base.y,
+                    z: base.x,
+                },
+                height,
+                scale,
+                base_scale,
+            )
+            .remap(Tree::z(), Tree::y(), Tree::x())
+    }
+
+    /// Scales `x` and `z` around `base`, from `base_scale` at `y = base.y`
+    /// to `scale` at `y = base.y + height`.
+    pub fn taper_xz_y(
+        self,
+        base: TreeVec3,
+        height: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::x(), Tree::z(), Tree::y())
+            .taper_xy_z(
+                TreeVec3 {
+                    x: base.x,
+                    y: base.z,
+                    z: base.y,
+                },
+                height,
+                scale,
+                base_scale,
+            )
+            .remap(Tree::x(), Tree::z(), Tree::y())
+    }
+
+    /// Shifts `y` by `base_offset` at `x = base.x`, changing linearly to
+    /// `offset` at `x = base.x + height`.
+    pub fn shear_y_x(
+        self,
+        base: TreeVec2,
+        height: impl Into<TreeFloat>,
+        offset: impl Into<TreeFloat>,
+        base_offset: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::y(), Tree::x(), Tree::z())
+            .shear_x_y(
+                TreeVec2 {
+                    x: base.y,
+                    y: base.x,
+                },
+                height,
+                offset,
+                base_offset,
+            )
+            .remap(Tree::y(), Tree::x(), Tree::z())
+    }
+
+    /// Shifts `x` by `base_offset` at `z = base.z`, changing linearly to
+    /// `offset` at `z = base.z + height`.
+    pub fn shear_x_z(
+        self,
+        base: TreeVec3,
+        height: impl Into<TreeFloat>,
+        offset: impl Into<TreeFloat>,
+        base_offset: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::x(), Tree::z(), Tree::y())
+            .shear_x_y(
+                TreeVec2 {
+                    x: base.x,
+                    y: base.z,
+                },
+                height,
+                offset,
+                base_offset,
+            )
+            .remap(Tree::x(), Tree::z(), Tree::y())
+    }
+
+    /// Shifts `z` by `base_offset` at `x = base.x`, changing linearly to
+    /// `offset` at `x = base.x + height`.
+    pub fn shear_z_x(
+        self,
+        base: TreeVec3,
+        height: impl Into<TreeFloat>,
+        offset: impl Into<TreeFloat>,
+        base_offset: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::y(), Tree::z(), Tree::x())
+            .shear_x_y(
+                TreeVec2 {
+                    x: base.z,
+                    y: base.x,
+                },
+                height,
+                offset,
+                base_offset,
+            )
+            .remap(Tree::z(), Tree::x(), Tree::y())
+    }
+
+    /// Shifts `y` by `base_offset` at `z = base.z`, changing linearly to
+    /// `offset` at `z = base.z + height`.
+    pub fn shear_y_z(
+        self,
+        base: TreeVec3,
+        height: impl Into<TreeFloat>,
+        offset: impl Into<TreeFloat>,
+        base_offset: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::z(), Tree::x(), Tree::y())
+            .shear_x_y(
+                TreeVec2 {
+                    x: base.y,
+                    y: base.z,
+                },
+                height,
+                offset,
+                base_offset,
+            )
+            .remap(Tree::y(), Tree::z(), Tree::x())
+    }
+
+    /// Shifts `z` by `base_offset` at `y = base.y`, changing linearly to
+    /// `offset` at `y = base.y + height`.
+    pub fn shear_z_y(
+        self,
+        base: TreeVec3,
+        height: impl Into<TreeFloat>,
+        offset: impl Into<TreeFloat>,
+        base_offset: impl Into<TreeFloat>,
+    ) -> Self {
+        self.remap(Tree::z(), Tree::y(), Tree::x())
+            .shear_x_y(
+                TreeVec2 {
+                    x: base.z,
+                    y: base.y,
+                },
+                height,
+                offset,
+                base_offset,
+            )
+            .remap(Tree::z(), Tree::y(), Tree::x())
+    }
+}
+
+/// Tori around further axes.
+///
+/// Like [`torus_z()`](Tree::torus_z) but with the hole along another axis.
+/// They turn a torus around the origin so that its axis points along `x`
+/// resp. `y` and then move it to `center`.
+impl Tree {
+    /// Returns a torus with the major radius `ro` and the minor radius `ri`
+    /// whose hole is along the `x` axis.
+    pub fn torus_x(
+        ro: impl Into<TreeFloat>,
+        ri: impl Into<TreeFloat>,
+        center: TreeVec3,
+    ) -> Self {
+        Tree::torus_z(ro, ri, TreeVec3::default())
+            .remap(Tree::z(), Tree::y(), Tree::x())
+            .moveit(center)
+    }
+
+    /// Returns a torus with the major radius `ro` and the minor radius `ri`
+    /// whose hole is along the `y` axis.
+    pub fn torus_y(
+        ro: impl Into<TreeFloat>,
+        ri: impl Into<TreeFloat>,
+        center: TreeVec3,
+    ) -> Self {
+        Tree::torus_z(ro, ri, TreeVec3::default())
+            .remap(Tree::x(), Tree::z(), Tree::y())
+            .moveit(center)
+    }
+}
+
+/// Polar coordinates.
+impl Tree {
+    /// Wraps a pattern authored in polar space around the `z` axis.
+    ///
+    /// The tree's `x` coordinate is used as the radius `sqrt(x² + y²)` and
+    /// its `y` coordinate as the angle `atan2(y, x)`, in radians from `-π`
+    /// to `π`. E.g. a stripe along `x` around `y = 0` becomes a spoke along
+    /// the positive `x` axis.
+    ///
+    /// Distances along `y` get scaled by the radius. The result is thus no
+    /// longer a distance field.
+    pub fn to_polar_xy(self) -> Self {
+        let radius = Tree::length2(&TreeVec2 {
+            x: Tree::x(),
+            y: Tree::y(),
+        });
+
+        self.remap(radius, Tree::y().atan2(Tree::x()), Tree::z())
+    }
+
+    /// Unwraps the tree into polar space, the inverse of
+    /// [`to_polar_xy()`](Tree::to_polar_xy).
+    ///
+    /// In the result `x` is the radius and `y` the angle in radians.
+    pub fn from_polar_xy(self) -> Self {
+        self.remap(
+            Tree::x() * Tree::y().cos(),
+            Tree::x() * Tree::y().sin(),
+            Tree::z(),
+        )
+    }
+}
+
+/// Sweeps & extrusions.
+impl Tree {
+    /// Sweeps the 2D `profile` along the polyline `path`.
+    ///
+    /// Each segment of the path is an extrusion of the `profile`, capped
+    /// flat at both ends. The `profile`'s origin follows the path. Its
+    /// orientation around the segment's direction is chosen so that it
+    /// varies continuously with that direction.
+    ///
+    /// Spheres of radius `r` are placed at every point of the path to fill
+    /// the gaps between segments at corners. For a circular `profile` of
+    /// radius `r` this yields a chain of capsules, i.e. a smooth pipe.
+    ///
+    /// Segments are simply unioned. At sharp corners neighboring segments
+    /// overlap. This is harmless for an implicit union but the field is not
+    /// an exact distance there.
+    pub fn sweep(
+        profile: Tree,
+        path: &[TreeVec3],
+        r: impl Into<TreeFloat>,
+    ) -> Self {
+        let r = r.into();
+        let segments = path
+            .windows(2)
+            .map(|pair| sweep_segment(profile.clone(), &pair[0], &pair[1]));
+        let joints = path
+            .iter()
+            .map(|point| Tree::sphere(r.clone(), point.clone()));
+
+        Tree::union_all(segments.chain(joints))
+    }
+
+    /// Extrudes the 2D `profile` from `zmin` to `zmax` with tapering walls.
+    ///
+    /// The profile is scaled around the `z` axis by `base_scale` at `zmin`
+    /// and by `scale` at `zmax`, changing linearly in between. E.g. a
+    /// `scale` smaller than `base_scale` gives the walls a draft, so the
+    /// part narrows towards the top.
+    ///
+    /// This is [`extrude_z()`](Tree::extrude_z) followed by
+    /// [`taper_xy_z()`](Tree::taper_xy_z).
+    pub fn extrude_taper_z(
+        profile: Tree,
+        zmin: impl Into<TreeFloat>,
+        zmax: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
+    ) -> Self {
+        let zmin = zmin.into();
+        let zmax = zmax.into();
+        Tree::extrude_z(profile, zmin.clone(), zmax.clone()).taper_xy_z(
+            TreeVec3 {
+                x: 0.0.into(),
+                y: 0.0.into(),
+                z: zmin.clone(),
+            },
+            zmax - zmin,
+            scale,
+            base_scale,
+        )
     }
 }
 
+/// Extrudes the 2D `profile` from `a` to `b`.
+fn sweep_segment(profile: Tree, a: &TreeVec3, b: &TreeVec3) -> Tree {
+    let direction = TreeVec3 {
+        x: b.x.clone() - a.x.clone(),
+        y: b.y.clone() - a.y.clone(),
+        z: b.z.clone() - a.z.clone(),
+    };
+    let length = Tree::length3(&direction);
+    let n = direction.normalized();
+
+    // Orthonormal basis around `n` without branches; see "Building an
+    // Orthonormal Basis, Revisited", Duff et al. 2017.
+    let sign = Tree::step(0.0, n.z.clone()) * Tree::from(2.0) - Tree::from(1.0);
+    let k = Tree::from(-1.0) / (sign.clone() + n.z.clone());
+    let m = n.x.clone() * n.y.clone() * k.clone();
+    let u = TreeVec3 {
+        x: Tree::from(1.0) + sign.clone() * n.x.square() * k.clone(),
+        y: sign.clone() * m.clone(),
+        z: -(sign.clone() * n.x.clone()),
+    };
+    let v = TreeVec3 {
+        x: m,
+        y: sign + n.y.square() * k,
+        z: -n.y.clone(),
+    };
+
+    let offset = TreeVec3 {
+        x: Tree::x() - a.x.clone(),
+        y: Tree::y() - a.y.clone(),
+        z: Tree::z() - a.z.clone(),
+    };
+    let axial = offset.dot(&n);
+    let caps = (-axial.clone()).max(axial - length);
+
+    profile
+        .remap(offset.dot(&u), offset.dot(&v), 0.0.into())
+        .max(caps)
+}
+
+/// Heightfields.
+impl Tree {
+    /// Creates a solid whose top surface follows a grid of `heights`.
+    ///
+    /// `heights` holds `width` × `height` samples, row by row, with `x`
+    /// varying fastest. The first row lies at the lower `y` of `region`. The
+    /// samples are spread evenly over `region` so the outer ones sit
+    /// exactly on its edges. E.g. the pixel values of a grayscale image.
+    ///
+    /// The solid spans `region` in `x` and `y`. In `z` it extends from `0`
+    /// up to the bilinearly interpolated heights, multiplied by `z_scale`.
+    ///
+    /// # Performance
+    ///
+    /// Interpolation is built as a sum over one tent function per sample.
+    /// The tree thus has a few nodes per sample and evaluating it touches
+    /// all of them. Downsample large images to a few thousand samples
+    /// before passing them in.
+    ///
+    /// # Panics
+    ///
+    /// If `heights.len()` is not `width * height`.
+    pub fn heightfield(
+        heights: &[f32],
+        width: u32,
+        height: u32,
+        region: &Region2,
+        z_scale: f32,
+    ) -> Self {
+        assert_eq!(
+            heights.len(),
+            width as usize * height as usize,
+            "there must be width * height heights"
+        );
+
+        let (min, max, size) = (region.min(), region.max(), region.size());
+
+        // Tent function of each sample along one axis. Their weighted sum is
+        // the linear interpolation of the weights.
+        let tents = |coordinate: Tree, axis: usize, samples: u32| {
+            let spacing = size[axis] / samples.saturating_sub(1).max(1) as f32;
+            let position = ((coordinate - min[axis].into()) / spacing.into())
+                .clamp(0.0, (samples.max(1) - 1) as f32);
+            (0..samples)
+                .map(|index| {
+                    (Tree::from(1.0)
+                        - (position.clone() - (index as f32).into()).abs())
+                    .max(0.0.into())
+                })
+                .collect::<Vec<_>>()
+        };
+        let x_tents = tents(Tree::x(), 0, width);
+        let y_tents = tents(Tree::y(), 1, height);
+
+        // Zero samples contribute nothing.
+        let surface = sum(heights
+            .chunks(width.max(1) as usize)
+            .zip(y_tents)
+            .map(|(row, y_tent)| {
+                y_tent
+                    * sum(row.iter().zip(&x_tents).filter_map(
+                        |(&sample, x_tent)| {
+                            (sample != 0.0)
+                                .then(|| Tree::from(sample) * x_tent.clone())
+                        },
+                    ))
+            }))
+            * z_scale.into();
+
+        let footprint = Tree::max_all([
+            Tree::from(min[0]) - Tree::x(),
+            Tree::x() - max[0].into(),
+            Tree::from(min[1]) - Tree::y(),
+            Tree::y() - max[1].into(),
+        ]);
+
+        Tree::max_all([Tree::z() - surface, -Tree::z(), footprint])
+    }
+}
+
+/// Returns the sum of `terms` or `0` if there are none.
+fn sum(terms: impl Iterator<Item = Tree>) -> Tree {
+    terms.reduce(|a, b| a + b).unwrap_or_else(|| 0.0.into())
+}
+
 include!("transforms.rs");
 include!("text.rs");
+
+/// Horizontal alignment of text relative to its position.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TextAlign {
+    /// The text starts at the position, like with [`Tree::text()`].
+    #[default]
+    Left,
+    /// The text is centered on the position.
+    Center,
+    /// The text ends at the position.
+    Right,
+}
+
+/// Text layout.
+impl Tree {
+    /// Places `txt` with its baseline at `pos.y`, aligned horizontally to
+    /// `pos.x` as given by `align`.
+    ///
+    /// Centered and right aligned text is measured with
+    /// [`text_bounds()`](Tree::text_bounds).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidText`] if `txt` contains a NUL byte.
+    pub fn text_aligned(
+        txt: impl Into<Vec<u8>>,
+        pos: TreeVec2,
+        align: TextAlign,
+    ) -> Result<Self> {
+        let txt = txt.into();
+        let offset = match align {
+            TextAlign::Left => 0.0,
+            TextAlign::Center => {
+                let bounds = Tree::text_bounds(txt.clone())?;
+                -0.5 * (bounds.min()[0] + bounds.max()[0])
+            }
+            TextAlign::Right => -Tree::text_bounds(txt.clone())?.max()[0],
+        };
+
+        Tree::text(
+            txt,
+            TreeVec2 {
+                x: pos.x + offset.into(),
+                y: pos.y,
+            },
+        )
+    }
+
+    /// Places the lines of `txt` below each other, each aligned as given by
+    /// `align`.
+    ///
+    /// The first line's baseline is at `pos.y`. Each further line moves down
+    /// by `line_height`. Empty lines only advance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidText`] if `txt` contains a NUL byte.
+    pub fn text_block(
+        txt: &str,
+        pos: TreeVec2,
+        line_height: f32,
+        align: TextAlign,
+    ) -> Result<Self> {
+        let lines = txt
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(index, line)| {
+                Tree::text_aligned(
+                    line,
+                    TreeVec2 {
+                        x: pos.x.clone(),
+                        y: pos.y.clone() - (index as f32 * line_height).into(),
+                    },
+                    align,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Tree::union_all(lines))
+    }
+
+    /// Returns the extents of `txt` as placed by [`text()`](Tree::text) at
+    /// the origin.
+    ///
+    /// The baseline is at `y = 0`. Capital letters of `libfive`'s built-in
+    /// font are about one unit tall.
+    ///
+    /// `libfive` does not expose its font metrics. The extents are hence
+    /// those of the shape, found with [`bounds()`](Tree::bounds). They may
+    /// exceed the glyphs by about 1/64th of the text's size and leading or
+    /// trailing spaces do not count.
+    ///
+    /// Returns an empty region at the origin if `txt` has no visible
+    /// glyphs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidText`] if `txt` contains a NUL byte.
+    pub fn text_bounds(txt: impl Into<Vec<u8>>) -> Result<Region2> {
+        let txt = txt.into();
+        let hint =
+            Region3::new(-1.0, txt.len() as f32 + 1.0, -1.0, 2.0, -1.0, 1.0);
+        let text = Tree::text(txt, TreeVec2::default())?;
+
+        Ok(
+            match text.bounds(&hint).and_then(|coarse| text.bounds(&coarse)) {
+                Some(bounds) => {
+                    let (min, max) = (bounds.min(), bounds.max());
+                    Region2::new(min[0], max[0], min[1], max[1])
+                }
+                None => Region2::new(0.0, 0.0, 0.0, 0.0),
+            },
+        )
+    }
+}
@@ -0,0 +1,176 @@
+use crate::*;
+use std::io::{self, Read, Write};
+
+/// Size of the header of a binary STL file, in bytes.
+const BINARY_HEADER_LEN: usize = 80;
+
+/// Text at the start of the header of binary STL files we write.
+const BINARY_HEADER: &[u8] = b"binary STL written by libfive-rs";
+
+/// Encoding of an [`STL`](https://en.wikipedia.org/wiki/STL_(file_format))
+/// file.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum StlFormat {
+    /// Compact binary encoding. Each triangle takes 50 bytes.
+    #[default]
+    Binary,
+    /// Human readable text encoding. Several times larger than
+    /// [`Binary`](StlFormat::Binary).
+    Ascii,
+}
+
+/// Writes `mesh` as STL in the given `format` to `writer`.
+pub(crate) fn write_stl<T: Point3>(
+    mesh: &TriangleMesh<T>,
+    writer: impl Write,
+    format: StlFormat,
+) -> io::Result<()> {
+    match format {
+        StlFormat::Binary => write_binary_stl(mesh, writer),
+        StlFormat::Ascii => write_ascii_stl(mesh, writer),
+    }
+}
+
+/// Name of the solid in ASCII STL files we write.
+const ASCII_SOLID_NAME: &str = "libfive";
+
+/// Writes `mesh` as ASCII STL to `writer`.
+///
+/// Face normals are computed from the triangles' winding. Degenerate
+/// triangles get a zero normal.
+pub(crate) fn write_ascii_stl<T: Point3>(
+    mesh: &TriangleMesh<T>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    writeln!(writer, "solid {}", ASCII_SOLID_NAME)?;
+
+    for vertices in triangle_positions(mesh) {
+        let [nx, ny, nz] = face_normal(&vertices);
+        writeln!(writer, "  facet normal {:e} {:e} {:e}", nx, ny, nz)?;
+        writeln!(writer, "    outer loop")?;
+        for [x, y, z] in vertices {
+            writeln!(writer, "      vertex {:e} {:e} {:e}", x, y, z)?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+
+    writeln!(writer, "endsolid {}", ASCII_SOLID_NAME)
+}
+
+/// Writes `mesh` as binary STL to `writer`.
+///
+/// Face normals are computed from the triangles' winding. Degenerate
+/// triangles get a zero normal.
+pub(crate) fn write_binary_stl<T: Point3>(
+    mesh: &TriangleMesh<T>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let mut header = [0u8; BINARY_HEADER_LEN];
+    header[..BINARY_HEADER.len()].copy_from_slice(BINARY_HEADER);
+    writer.write_all(&header)?;
+
+    let count = u32::try_from(mesh.triangles.len())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    writer.write_all(&count.to_le_bytes())?;
+
+    // Normal, three vertices & attribute byte count.
+    let mut record = [0u8; 12 * 4 + 2];
+    for vertices in triangle_positions(mesh) {
+        let normal = face_normal(&vertices);
+
+        for (index, value) in
+            normal.iter().chain(vertices.iter().flatten()).enumerate()
+        {
+            record[index * 4..index * 4 + 4]
+                .copy_from_slice(&value.to_le_bytes());
+        }
+        writer.write_all(&record)?;
+    }
+
+    Ok(())
+}
+
+/// Returns an iterator over the positions of the vertices of each triangle
+/// of `mesh`.
+fn triangle_positions<T: Point3>(
+    mesh: &TriangleMesh<T>,
+) -> impl Iterator<Item = [[f32; 3]; 3]> + '_ {
+    mesh.triangles.iter().map(|triangle| {
+        triangle.map(|index| {
+            let position = &mesh.positions[index as usize];
+            [position.x(), position.y(), position.z()]
+        })
+    })
+}
+
+/// Returns the unit normal of the triangle `[a, b, c]`, wound
+/// counter-clockwise, or a zero vector if the triangle is degenerate.
+fn face_normal([a, b, c]: &[[f32; 3]; 3]) -> [f32; 3] {
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let normal = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+
+    let length = normal.iter().map(|c| c * c).sum::<f32>().sqrt();
+    if length > 0.0 {
+        normal.map(|c| c / length)
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// Reads a binary [`STL`](https://en.wikipedia.org/wiki/STL_(file_format))
+/// stream into an indexed [`TriangleMesh`].
+///
+/// STL stores every triangle with its own copy of its three vertices.
+/// Vertices with bitwise identical positions are merged so the resulting
+/// mesh is indexed like the ones returned by
+/// [`Tree::to_triangle_mesh()`](Tree::to_triangle_mesh).
+pub(crate) fn read_binary_stl<T: Point3>(
+    mut reader: impl Read,
+) -> io::Result<TriangleMesh<T>> {
+    let mut header = [0u8; BINARY_HEADER_LEN];
+    reader.read_exact(&mut header)?;
+
+    let mut count = [0u8; 4];
+    reader.read_exact(&mut count)?;
+    let count = u32::from_le_bytes(count) as usize;
+
+    let mut indices = HashMap::<[u32; 3], u32>::new();
+    let mut positions = Vec::new();
+    let mut triangles = Vec::with_capacity(count);
+
+    // Normal, three vertices & attribute byte count.
+    let mut record = [0u8; 12 * 4 + 2];
+    for _ in 0..count {
+        reader.read_exact(&mut record)?;
+
+        let mut triangle = [0u32; 3];
+        for (vertex, index) in triangle.iter_mut().enumerate() {
+            let offset = 12 + vertex * 12;
+            let bits = [0, 4, 8].map(|component| {
+                let start = offset + component;
+                u32::from_le_bytes(record[start..start + 4].try_into().unwrap())
+            });
+
+            *index = *indices.entry(bits).or_insert_with(|| {
+                positions.push(T::new(
+                    f32::from_bits(bits[0]),
+                    f32::from_bits(bits[1]),
+                    f32::from_bits(bits[2]),
+                ));
+                (positions.len() - 1) as _
+            });
+        }
+        triangles.push(triangle);
+    }
+
+    Ok(TriangleMesh {
+        positions,
+        triangles,
+    })
+}
@@ -0,0 +1,179 @@
+use crate::*;
+use std::io::{self, Write};
+
+/// Rule deciding which parts of a path are filled.
+///
+/// See the [SVG specification](https://www.w3.org/TR/SVG11/painting.html#FillRuleProperty).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses the path an odd number of
+    /// times. Holes stay empty regardless of their winding.
+    #[default]
+    EvenOdd,
+    /// A point is inside if the path winds around it a non-zero number of
+    /// times.
+    NonZero,
+}
+
+/// Appearance of the shapes in an
+/// [`SVG`](https://en.wikipedia.org/wiki/Scalable_Vector_Graphics) file.
+///
+/// The default is a thin black outline without fill.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgStyle {
+    /// Width of the outlines, in the units of the shape.
+    pub stroke_width: f32,
+    /// Paint of the outlines, e.g. `"black"`, `"#ff8000"` or `"none"`.
+    pub stroke: String,
+    /// Paint of the shapes' interiors, e.g. `"black"`, `"#ff8000"` or
+    /// `"none"`.
+    pub fill: String,
+    /// Rule deciding which parts are filled.
+    pub fill_rule: FillRule,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        Self {
+            stroke_width: 0.01,
+            stroke: "black".to_string(),
+            fill: "none".to_string(),
+            fill_rule: FillRule::default(),
+        }
+    }
+}
+
+/// Writes `contours` as an
+/// [`SVG`](https://en.wikipedia.org/wiki/Scalable_Vector_Graphics) document
+/// covering `region` to `writer`.
+///
+/// Like `libfive`'s own SVG export, one unit is one SVG user unit and the
+/// `y` axis is flipped so the slice appears upright.
+///
+/// Each outline becomes a `<path>` that also holds the holes directly inside
+/// it. Hence holes are cut out when the path is filled.
+pub(crate) fn write_svg<T: Point2>(
+    contours: &[Contour<T>],
+    region: &Region2,
+    style: &SvgStyle,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    write_header(region, &mut writer)?;
+    write_paths(contours, style, &mut writer)?;
+    writeln!(writer, "</svg>")
+}
+
+/// Writes the slices in `layers`, each a `z` height and its contours, as
+/// an SVG document covering `region` to `writer`.
+///
+/// Each slice becomes a `<g>` group with a `data-z` attribute. The groups
+/// are ordered by ascending `z` so upper slices are drawn over lower ones.
+/// Their outlines are colored by height, from blue for the lowest to red
+/// for the highest slice.
+pub(crate) fn write_svg_layers<T: Point2>(
+    layers: &[(f32, Vec<Contour<T>>)],
+    region: &Region2,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    write_header(region, &mut writer)?;
+
+    let mut order = (0..layers.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| layers[a].0.total_cmp(&layers[b].0));
+
+    for (rank, &index) in order.iter().enumerate() {
+        let (z, contours) = &layers[index];
+        let t = rank as f32 / (layers.len() - 1).max(1) as f32;
+        let style = SvgStyle {
+            stroke: format!("hsl({}, 100%, 45%)", 240.0 * (1.0 - t)),
+            ..Default::default()
+        };
+
+        writeln!(writer, r#"<g data-z="{}">"#, z)?;
+        write_paths(contours, &style, &mut writer)?;
+        writeln!(writer, "</g>")?;
+    }
+
+    writeln!(writer, "</svg>")
+}
+
+/// Writes the opening `<svg>` tag for a document covering `region`.
+fn write_header(region: &Region2, mut writer: impl Write) -> io::Result<()> {
+    let (min, max, size) = (region.min(), region.max(), region.size());
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+        size[0], size[1], min[0], -max[1], size[0], size[1],
+    )
+}
+
+/// Writes a `<path>` per outline in `contours`, including the holes
+/// directly inside it.
+fn write_paths<T: Point2>(
+    contours: &[Contour<T>],
+    style: &SvgStyle,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let nodes = classify_contours(contours);
+    for (contour, node) in contours.iter().zip(&nodes) {
+        if node.is_hole() {
+            continue;
+        }
+
+        let data = std::iter::once(contour)
+            .chain(node.children.iter().map(|&child| &contours[child]))
+            .map(path_data)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(
+            writer,
+            r#"<path d="{}" fill="{}" fill-rule="{}" stroke="{}" stroke-width="{}"/>"#,
+            data,
+            escape(&style.fill),
+            match style.fill_rule {
+                FillRule::EvenOdd => "evenodd",
+                FillRule::NonZero => "nonzero",
+            },
+            escape(&style.stroke),
+            style.stroke_width,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Escapes `value` for use in an attribute.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
+
+/// Returns the SVG path data for `contour`, with `y` flipped.
+///
+/// Closed contours, i.e. those whose last point equals their first one, end
+/// with a `Z` command instead of the repeated point.
+fn path_data<T: Point2>(contour: &Contour<T>) -> String {
+    let (points, closed) = crate::contour::split_closed(contour);
+
+    let mut data = points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            format!(
+                "{}{} {}",
+                if index == 0 { 'M' } else { 'L' },
+                point.x(),
+                -point.y()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    if closed {
+        data.push_str(" Z");
+    }
+
+    data
+}
@@ -0,0 +1,74 @@
+use std::{
+    collections::hash_map::RandomState,
+    fs,
+    hash::{BuildHasher, Hasher},
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A file in a private, temporary directory, for the `libfive` calls that
+/// only read from or write to files.
+///
+/// The directory is created with a random name and, on Unix, only the
+/// current user can access it. Its creation fails if anything already
+/// exists at its path. Hence no other user can plant a file or symlink
+/// that `libfive` would follow. The file is created inside of it and both
+/// are removed again on drop.
+pub(crate) struct TempFile {
+    directory: PathBuf,
+    path: PathBuf,
+}
+
+impl TempFile {
+    /// Creates an empty file with the given `extension`.
+    pub(crate) fn new(extension: &str) -> io::Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        const ATTEMPTS: usize = 8;
+
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+        for _ in 0..ATTEMPTS {
+            // Randomly keyed per call.
+            let random = RandomState::new().build_hasher().finish();
+            let directory = std::env::temp_dir().join(format!(
+                "libfive-{}-{}-{:016x}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed),
+                random
+            ));
+
+            match builder.create(&directory) {
+                Ok(()) => {}
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                    continue
+                }
+                Err(error) => return Err(error),
+            }
+
+            let path = directory.join(format!("shape.{}", extension));
+            let file = Self { directory, path };
+            fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&file.path)?;
+
+            return Ok(file);
+        }
+
+        Err(io::ErrorKind::AlreadyExists.into())
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+        let _ = fs::remove_dir(&self.directory);
+    }
+}
@@ -1,8 +1,14 @@
 /// # Text <a name="text"></a>
 impl Tree {
-    pub fn text(txt: impl Into<Vec<u8>>, pos: TreeVec2) -> Self {
-        let txt = std::ffi::CString::new(txt).unwrap();
-        Self(unsafe {
+    /// Renders `txt` with its baseline starting at `pos`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidText`] if `txt` contains a NUL byte.
+    pub fn text(txt: impl Into<Vec<u8>>, pos: TreeVec2) -> Result<Self> {
+        let txt =
+            std::ffi::CString::new(txt).map_err(|_| Error::InvalidText)?;
+        Ok(Self(unsafe {
             sys::text(
                 txt.as_ptr(),
                 sys::tvec2 {
@@ -10,6 +16,6 @@ impl Tree {
                     y: pos.y.0,
                 },
             )
-        })
+        }))
     }
 }
@@ -13,16 +13,16 @@ impl Tree {
         })
     }
 
-    pub fn reflect_x(self, x0: TreeFloat) -> Self {
-        Self(unsafe { sys::reflect_x(self.0, x0.0) })
+    pub fn reflect_x(self, x0: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::reflect_x(self.0, x0.into().0) })
     }
 
-    pub fn reflect_y(self, y0: TreeFloat) -> Self {
-        Self(unsafe { sys::reflect_y(self.0, y0.0) })
+    pub fn reflect_y(self, y0: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::reflect_y(self.0, y0.into().0) })
     }
 
-    pub fn reflect_z(self, z0: TreeFloat) -> Self {
-        Self(unsafe { sys::reflect_z(self.0, z0.0) })
+    pub fn reflect_z(self, z0: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::reflect_z(self.0, z0.into().0) })
     }
 
     pub fn reflect_xy(self) -> Self {
@@ -49,16 +49,28 @@ impl Tree {
         Self(unsafe { sys::symmetric_z(self.0) })
     }
 
-    pub fn scale_x(self, sx: TreeFloat, x0: TreeFloat) -> Self {
-        Self(unsafe { sys::scale_x(self.0, sx.0, x0.0) })
+    pub fn scale_x(
+        self,
+        sx: impl Into<TreeFloat>,
+        x0: impl Into<TreeFloat>,
+    ) -> Self {
+        Self(unsafe { sys::scale_x(self.0, sx.into().0, x0.into().0) })
     }
 
-    pub fn scale_y(self, sy: TreeFloat, y0: TreeFloat) -> Self {
-        Self(unsafe { sys::scale_y(self.0, sy.0, y0.0) })
+    pub fn scale_y(
+        self,
+        sy: impl Into<TreeFloat>,
+        y0: impl Into<TreeFloat>,
+    ) -> Self {
+        Self(unsafe { sys::scale_y(self.0, sy.into().0, y0.into().0) })
     }
 
-    pub fn scale_z(self, sz: TreeFloat, z0: TreeFloat) -> Self {
-        Self(unsafe { sys::scale_z(self.0, sz.0, z0.0) })
+    pub fn scale_z(
+        self,
+        sz: impl Into<TreeFloat>,
+        z0: impl Into<TreeFloat>,
+    ) -> Self {
+        Self(unsafe { sys::scale_z(self.0, sz.into().0, z0.into().0) })
     }
 
     pub fn scale_xyz(self, s: TreeVec3, center: TreeVec3) -> Self {
@@ -79,11 +91,15 @@ impl Tree {
         })
     }
 
-    pub fn rotate_x(self, angle: TreeFloat, center: TreeVec3) -> Self {
+    pub fn rotate_x(
+        self,
+        angle: impl Into<TreeFloat>,
+        center: TreeVec3,
+    ) -> Self {
         Self(unsafe {
             sys::rotate_x(
                 self.0,
-                angle.0,
+                angle.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -93,11 +109,15 @@ impl Tree {
         })
     }
 
-    pub fn rotate_y(self, angle: TreeFloat, center: TreeVec3) -> Self {
+    pub fn rotate_y(
+        self,
+        angle: impl Into<TreeFloat>,
+        center: TreeVec3,
+    ) -> Self {
         Self(unsafe {
             sys::rotate_y(
                 self.0,
-                angle.0,
+                angle.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -107,11 +127,15 @@ impl Tree {
         })
     }
 
-    pub fn rotate_z(self, angle: TreeFloat, center: TreeVec3) -> Self {
+    pub fn rotate_z(
+        self,
+        angle: impl Into<TreeFloat>,
+        center: TreeVec3,
+    ) -> Self {
         Self(unsafe {
             sys::rotate_z(
                 self.0,
-                angle.0,
+                angle.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -124,9 +148,9 @@ impl Tree {
     pub fn taper_x_y(
         self,
         base: TreeVec2,
-        h: TreeFloat,
-        scale: TreeFloat,
-        base_scale: TreeFloat,
+        h: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::taper_x_y(
@@ -135,9 +159,9 @@ impl Tree {
                     x: base.x.0,
                     y: base.y.0,
                 },
-                h.0,
-                scale.0,
-                base_scale.0,
+                h.into().0,
+                scale.into().0,
+                base_scale.into().0,
             )
         })
     }
@@ -145,9 +169,9 @@ impl Tree {
     pub fn taper_xy_z(
         self,
         base: TreeVec3,
-        height: TreeFloat,
-        scale: TreeFloat,
-        base_scale: TreeFloat,
+        height: impl Into<TreeFloat>,
+        scale: impl Into<TreeFloat>,
+        base_scale: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::taper_xy_z(
@@ -157,9 +181,9 @@ impl Tree {
                     y: base.y.0,
                     z: base.z.0,
                 },
-                height.0,
-                scale.0,
-                base_scale.0,
+                height.into().0,
+                scale.into().0,
+                base_scale.into().0,
             )
         })
     }
@@ -167,9 +191,9 @@ impl Tree {
     pub fn shear_x_y(
         self,
         base: TreeVec2,
-        height: TreeFloat,
-        offset: TreeFloat,
-        base_offset: TreeFloat,
+        height: impl Into<TreeFloat>,
+        offset: impl Into<TreeFloat>,
+        base_offset: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::shear_x_y(
@@ -178,9 +202,9 @@ impl Tree {
                     x: base.x.0,
                     y: base.y.0,
                 },
-                height.0,
-                offset.0,
-                base_offset.0,
+                height.into().0,
+                offset.into().0,
+                base_offset.into().0,
             )
         })
     }
@@ -188,8 +212,8 @@ impl Tree {
     pub fn repel(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::repel(
@@ -199,8 +223,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -208,8 +232,8 @@ impl Tree {
     pub fn repel_x(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::repel_x(
@@ -219,8 +243,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -228,8 +252,8 @@ impl Tree {
     pub fn repel_y(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::repel_y(
@@ -239,8 +263,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -248,8 +272,8 @@ impl Tree {
     pub fn repel_z(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::repel_z(
@@ -259,8 +283,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -268,8 +292,8 @@ impl Tree {
     pub fn repel_xy(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::repel_xy(
@@ -279,8 +303,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -288,8 +312,8 @@ impl Tree {
     pub fn repel_yz(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::repel_yz(
@@ -299,8 +323,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -308,8 +332,8 @@ impl Tree {
     pub fn repel_xz(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::repel_xz(
@@ -319,8 +343,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -328,8 +352,8 @@ impl Tree {
     pub fn attract(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::attract(
@@ -339,8 +363,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -348,8 +372,8 @@ impl Tree {
     pub fn attract_x(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::attract_x(
@@ -359,8 +383,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -368,8 +392,8 @@ impl Tree {
     pub fn attract_y(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::attract_y(
@@ -379,8 +403,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -388,8 +412,8 @@ impl Tree {
     pub fn attract_z(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::attract_z(
@@ -399,8 +423,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -408,8 +432,8 @@ impl Tree {
     pub fn attract_xy(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::attract_xy(
@@ -419,8 +443,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -428,8 +452,8 @@ impl Tree {
     pub fn attract_yz(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::attract_yz(
@@ -439,8 +463,8 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
@@ -448,8 +472,8 @@ impl Tree {
     pub fn attract_xz(
         self,
         locus: TreeVec3,
-        radius: TreeFloat,
-        exaggerate: TreeFloat,
+        radius: impl Into<TreeFloat>,
+        exaggerate: impl Into<TreeFloat>,
     ) -> Self {
         Self(unsafe {
             sys::attract_xz(
@@ -459,27 +483,27 @@ impl Tree {
                     y: locus.y.0,
                     z: locus.z.0,
                 },
-                radius.0,
-                exaggerate.0,
+                radius.into().0,
+                exaggerate.into().0,
             )
         })
     }
 
-    pub fn revolve_y(self, x0: TreeFloat) -> Self {
-        Self(unsafe { sys::revolve_y(self.0, x0.0) })
+    pub fn revolve_y(self, x0: impl Into<TreeFloat>) -> Self {
+        Self(unsafe { sys::revolve_y(self.0, x0.into().0) })
     }
 
     pub fn twirl_x(
         self,
-        amount: TreeFloat,
-        radius: TreeFloat,
+        amount: impl Into<TreeFloat>,
+        radius: impl Into<TreeFloat>,
         center: TreeVec3,
     ) -> Self {
         Self(unsafe {
             sys::twirl_x(
                 self.0,
-                amount.0,
-                radius.0,
+                amount.into().0,
+                radius.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -491,15 +515,15 @@ impl Tree {
 
     pub fn twirl_axis_x(
         self,
-        amount: TreeFloat,
-        radius: TreeFloat,
+        amount: impl Into<TreeFloat>,
+        radius: impl Into<TreeFloat>,
         center: TreeVec3,
     ) -> Self {
         Self(unsafe {
             sys::twirl_axis_x(
                 self.0,
-                amount.0,
-                radius.0,
+                amount.into().0,
+                radius.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -511,15 +535,15 @@ impl Tree {
 
     pub fn twirl_y(
         self,
-        amount: TreeFloat,
-        radius: TreeFloat,
+        amount: impl Into<TreeFloat>,
+        radius: impl Into<TreeFloat>,
         center: TreeVec3,
     ) -> Self {
         Self(unsafe {
             sys::twirl_y(
                 self.0,
-                amount.0,
-                radius.0,
+                amount.into().0,
+                radius.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -531,15 +555,15 @@ impl Tree {
 
     pub fn twirl_axis_y(
         self,
-        amount: TreeFloat,
-        radius: TreeFloat,
+        amount: impl Into<TreeFloat>,
+        radius: impl Into<TreeFloat>,
         center: TreeVec3,
     ) -> Self {
         Self(unsafe {
             sys::twirl_axis_y(
                 self.0,
-                amount.0,
-                radius.0,
+                amount.into().0,
+                radius.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -551,15 +575,15 @@ impl Tree {
 
     pub fn twirl_z(
         self,
-        amount: TreeFloat,
-        radius: TreeFloat,
+        amount: impl Into<TreeFloat>,
+        radius: impl Into<TreeFloat>,
         center: TreeVec3,
     ) -> Self {
         Self(unsafe {
             sys::twirl_z(
                 self.0,
-                amount.0,
-                radius.0,
+                amount.into().0,
+                radius.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -571,15 +595,15 @@ impl Tree {
 
     pub fn twirl_axis_z(
         self,
-        amount: TreeFloat,
-        radius: TreeFloat,
+        amount: impl Into<TreeFloat>,
+        radius: impl Into<TreeFloat>,
         center: TreeVec3,
     ) -> Self {
         Self(unsafe {
             sys::twirl_axis_z(
                 self.0,
-                amount.0,
-                radius.0,
+                amount.into().0,
+                radius.into().0,
                 sys::tvec3 {
                     x: center.x.0,
                     y: center.y.0,
@@ -589,4 +613,3 @@ impl Tree {
         })
     }
 }
-